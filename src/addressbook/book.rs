@@ -0,0 +1,141 @@
+use crate::functions::looks_like_nomic_address;
+use crate::functions::TableColumns;
+use crate::functions::validate_nomic_address;
+use crate::global::PROFILES_DIR;
+use eyre::eyre;
+use eyre::Result;
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tabled::builder::Builder;
+use tabled::settings::{Border, Modify, Style};
+use tabled::settings::object::Cell;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Entry {
+    pub address: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note:    String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags:    Vec<String>,
+}
+
+/// Labeled send destinations, stored in `PROFILES_DIR/address_book.toml`
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct AddressBook {
+    #[serde(default)]
+    entries: BTreeMap<String, Entry>,
+}
+
+impl AddressBook {
+
+    pub fn path() -> PathBuf {
+        PROFILES_DIR.join("address_book.toml")
+    }
+
+    /// Load the address book, an empty one if the file does not exist yet
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let toml_str = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Failed to read address book at {:?}", path))?;
+        toml::from_str(&toml_str)
+            .wrap_err_with(|| format!("Failed to parse address book at {:?}", path))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        let toml_str = toml::to_string(self)
+            .wrap_err("Failed to serialize address book to TOML")?;
+        fs::write(&path, toml_str)
+            .wrap_err_with(|| format!("Failed to write address book to {:?}", path))?;
+        Ok(())
+    }
+
+    pub fn add(
+        &mut self,
+        label:   &str,
+        address: &str,
+        note:    Option<String>,
+        tags:    Vec<String>,
+        force:   bool,
+    ) -> Result<()> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(eyre!("Label cannot be empty"));
+        }
+        if looks_like_nomic_address(label) {
+            return Err(eyre!("Label '{}' looks like an address, choose a name", label));
+        }
+        if self.entries.contains_key(label) && !force {
+            return Err(eyre!("'{}' is already in the address book. Use --force to replace it.", label));
+        }
+
+        let entry = Entry {
+            address: validate_nomic_address(address)?,
+            note:    note.unwrap_or_default(),
+            tags,
+        };
+        self.entries.insert(label.to_string(), entry);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, label: &str) -> Result<Entry> {
+        self.entries.remove(label)
+            .ok_or_else(|| eyre!("'{}' is not in the address book", label))
+    }
+
+    pub fn get(&self, label: &str) -> Option<&Entry> {
+        self.entries.get(label)
+    }
+
+    /// The label an address is stored under, if any
+    pub fn label(&self, address: &str) -> Option<&str> {
+        self.entries.iter()
+            .find(|(_, entry)| entry.address == address)
+            .map(|(label, _)| label.as_str())
+    }
+
+    pub fn contains_address(&self, address: &str) -> bool {
+        self.label(address).is_some()
+    }
+
+    pub fn table(&self, tag: Option<&str>) -> String {
+        let mut rows: Vec<TableColumns> = Vec::new();
+        rows.push(TableColumns::new(vec![ "Label", "Address", "Tags", "Note" ]));
+        for (label, entry) in &self.entries {
+            if let Some(tag) = tag {
+                if !entry.tags.iter().any(|t| t == tag) {
+                    continue;
+                }
+            }
+            rows.push(TableColumns::new(vec![
+                label.as_str(),
+                &entry.address,
+                &entry.tags.join(","),
+                &entry.note,
+            ]));
+        }
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([
+                row.cell0.clone(), row.cell1.clone(), row.cell2.clone(), row.cell3.clone()
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::blank())
+            .with(Modify::new(Cell::new(0, 0)).with(Border::new().set_bottom('-')))
+            .with(Modify::new(Cell::new(0, 1)).with(Border::new().set_bottom('-')))
+            .with(Modify::new(Cell::new(0, 2)).with(Border::new().set_bottom('-')))
+            .with(Modify::new(Cell::new(0, 3)).with(Border::new().set_bottom('-')))
+            ;
+
+        table.to_string()
+    }
+}
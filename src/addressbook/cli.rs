@@ -0,0 +1,72 @@
+use clap::{Parser, Subcommand};
+use crate::addressbook::AddressBook;
+use eyre::Result;
+
+#[derive(Parser)]
+#[command(about = "Manage labeled send destinations")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    #[command(about = "Add an address", visible_alias = "a")]
+    Add {
+        /// Label to send to, e.g. exchange-deposit
+        label: String,
+
+        /// nomic1... address
+        address: String,
+
+        /// Free text note
+        #[arg(short, long)]
+        note: Option<String>,
+
+        /// Tag, can be repeated
+        #[arg(short, long = "tag")]
+        tags: Vec<String>,
+
+        /// Replace an existing label
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    #[command(about = "Remove an address", visible_alias = "rm", aliases = ["r", "del", "delete"])]
+    Remove {
+        label: String,
+    },
+
+    #[command(about = "List addresses", visible_alias = "ls", aliases = ["l"])]
+    List {
+        /// Only list addresses with this tag
+        #[arg(short, long)]
+        tag: Option<String>,
+    },
+}
+
+impl Cli {
+    pub fn run(&self) -> Result<()> {
+        let mut book = AddressBook::load()?;
+        match &self.command {
+            Some(Command::Add { label, address, note, tags, force }) => {
+                book.add(label, address, note.clone(), tags.clone(), *force)?;
+                book.save()
+            },
+            Some(Command::Remove { label }) => {
+                let entry = book.remove(label)?;
+                book.save()?;
+                println!("Removed {} ({})", label, entry.address);
+                Ok(())
+            },
+            Some(Command::List { tag }) => {
+                println!("{}", book.table(tag.as_deref()));
+                Ok(())
+            },
+            None => {
+                println!("{}", book.table(None));
+                Ok(())
+            },
+        }
+    }
+}
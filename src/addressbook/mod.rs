@@ -0,0 +1,5 @@
+mod book;
+mod cli;
+
+pub use book::AddressBook;
+pub use cli::Cli;
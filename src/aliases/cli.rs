@@ -0,0 +1,60 @@
+use clap::{Parser, Subcommand};
+use crate::aliases::ValidatorAliases;
+use eyre::Result;
+
+#[derive(Parser)]
+#[command(about = "Manage validator aliases shared by all profiles")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    #[command(about = "Add an alias", visible_alias = "a")]
+    Add {
+        /// Alias to use in place of the address, e.g. my-validator
+        alias: String,
+
+        /// Validator address
+        address: String,
+
+        /// Free text note
+        #[arg(short, long)]
+        note: Option<String>,
+
+        /// Replace an existing alias
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    #[command(about = "Remove an alias", visible_alias = "rm", aliases = ["r", "del", "delete"])]
+    Remove {
+        alias: String,
+    },
+
+    #[command(about = "List aliases", visible_alias = "ls", aliases = ["l"])]
+    List,
+}
+
+impl Cli {
+    pub fn run(&self) -> Result<()> {
+        let mut aliases = ValidatorAliases::load()?;
+        match &self.command {
+            Some(Command::Add { alias, address, note, force }) => {
+                aliases.add(alias, address, note.clone(), *force)?;
+                aliases.save()
+            },
+            Some(Command::Remove { alias }) => {
+                let entry = aliases.remove(alias)?;
+                aliases.save()?;
+                println!("Removed {} ({})", alias, entry.address);
+                Ok(())
+            },
+            Some(Command::List) | None => {
+                println!("{}", aliases.table());
+                Ok(())
+            },
+        }
+    }
+}
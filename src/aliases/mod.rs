@@ -0,0 +1,5 @@
+mod cli;
+mod registry;
+
+pub use cli::Cli;
+pub use registry::ValidatorAliases;
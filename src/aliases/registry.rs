@@ -0,0 +1,133 @@
+use crate::functions::looks_like_nomic_address;
+use crate::functions::TableColumns;
+use crate::functions::validate_validator_address;
+use crate::global::PROFILES_DIR;
+use eyre::eyre;
+use eyre::Result;
+use eyre::WrapErr;
+use log::warn;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tabled::builder::Builder;
+use tabled::settings::{Border, Modify, Style};
+use tabled::settings::object::Rows;
+
+static SHARED: OnceCell<ValidatorAliases> = OnceCell::new();
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Entry {
+    pub address: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note:    String,
+}
+
+/// Validator nicknames shared by every profile, stored in `PROFILES_DIR/validator_aliases.toml`.
+/// Aliases are case-insensitive and stored lowercase.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ValidatorAliases {
+    #[serde(default)]
+    aliases: BTreeMap<String, Entry>,
+}
+
+impl ValidatorAliases {
+
+    pub fn path() -> PathBuf {
+        PROFILES_DIR.join("validator_aliases.toml")
+    }
+
+    /// Load the aliases, none if the file does not exist yet
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let toml_str = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Failed to read validator aliases at {:?}", path))?;
+        toml::from_str(&toml_str)
+            .wrap_err_with(|| format!("Failed to parse validator aliases at {:?}", path))
+    }
+
+    /// The aliases as loaded on first use in this run, empty if they cannot be read
+    pub fn shared() -> &'static Self {
+        SHARED.get_or_init(|| Self::load().unwrap_or_else(|e| {
+            warn!("Failed to load validator aliases: {}", e);
+            Self::default()
+        }))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        let toml_str = toml::to_string(self)
+            .wrap_err("Failed to serialize validator aliases to TOML")?;
+        fs::write(&path, toml_str)
+            .wrap_err_with(|| format!("Failed to write validator aliases to {:?}", path))?;
+        Ok(())
+    }
+
+    pub fn add(&mut self, alias: &str, address: &str, note: Option<String>, force: bool) -> Result<()> {
+        let alias = alias.trim().to_lowercase();
+        if alias.is_empty() {
+            return Err(eyre!("Alias cannot be empty"));
+        }
+        if alias.contains(',') || alias.contains(char::is_whitespace) {
+            return Err(eyre!("Alias '{}' cannot contain commas or whitespace", alias));
+        }
+        if looks_like_nomic_address(&alias) {
+            return Err(eyre!("Alias '{}' looks like an address, choose a name", alias));
+        }
+        if self.aliases.contains_key(&alias) && !force {
+            return Err(eyre!("'{}' is already an alias. Use --force to replace it.", alias));
+        }
+
+        let entry = Entry {
+            address: validate_validator_address(address)?,
+            note:    note.unwrap_or_default(),
+        };
+        self.aliases.insert(alias, entry);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, alias: &str) -> Result<Entry> {
+        self.aliases.remove(&alias.trim().to_lowercase())
+            .ok_or_else(|| eyre!("'{}' is not a validator alias", alias))
+    }
+
+    pub fn get(&self, alias: &str) -> Option<&Entry> {
+        self.aliases.get(&alias.trim().to_lowercase())
+    }
+
+    /// The address an alias points to
+    pub fn resolve(&self, alias: &str) -> Option<&str> {
+        self.get(alias).map(|entry| entry.address.as_str())
+    }
+
+    /// The alias for a validator address, if any
+    pub fn alias(&self, address: &str) -> Option<&str> {
+        self.aliases.iter()
+            .find(|(_, entry)| entry.address.eq_ignore_ascii_case(address))
+            .map(|(alias, _)| alias.as_str())
+    }
+
+    pub fn table(&self) -> String {
+        let mut rows: Vec<TableColumns> = Vec::new();
+        rows.push(TableColumns::new(vec![ "Alias", "Validator Address", "Note" ]));
+        for (alias, entry) in &self.aliases {
+            rows.push(TableColumns::new(vec![ alias.as_str(), &entry.address, &entry.note ]));
+        }
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([ row.cell0.clone(), row.cell1.clone(), row.cell2.clone() ]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::blank())
+            .with(Modify::new(Rows::first()).with(Border::new().set_bottom('-')))
+            ;
+
+        table.to_string()
+    }
+}
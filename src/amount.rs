@@ -0,0 +1,135 @@
+use crate::functions::NumberDisplay;
+use eyre::eyre;
+use eyre::Result;
+use std::str::FromStr;
+
+/// unom per nom
+pub const UNOM_PER_NOM: u64 = 1_000_000;
+
+/// A quantity of nom given on the command line.
+///
+/// Parsed exactly, without going through floats:
+/// - `1.5`, `1.5nom`    1.5 nom (up to 6 decimal places)
+/// - `1500000unom`      1.5 nom
+/// - `all`              everything available
+/// - `50%`              half of what is available (up to 4 decimal places)
+///
+/// Thousands separators (`1,000` or `1_000`) are accepted so figures
+/// can be copied straight out of a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Amount {
+    /// An absolute amount in unom
+    Unom(u64),
+    /// Everything available
+    All,
+    /// A share of what is available, in parts per million
+    Percent(u64),
+}
+
+/// Parses an unsigned decimal number into an integer scaled by `10^scale`.
+fn parse_decimal(value: &str, scale: u32) -> Result<u64> {
+    let value: String = value.chars().filter(|c| *c != ',' && *c != '_').collect();
+    let (integer, fraction) = value.split_once('.').unwrap_or((value.as_str(), ""));
+
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(eyre!("No digits"));
+    }
+    if !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(eyre!("Not a number: {}", value));
+    }
+    if fraction.len() > scale as usize {
+        return Err(eyre!("Too many decimal places (at most {})", scale));
+    }
+
+    let multiplier = 10u64.pow(scale);
+    let integer: u64 = if integer.is_empty() { 0 } else { integer.parse()? };
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u64>()? * 10u64.pow(scale - fraction.len() as u32)
+    };
+
+    integer
+        .checked_mul(multiplier)
+        .and_then(|n| n.checked_add(fraction))
+        .ok_or_else(|| eyre!("Amount too large"))
+}
+
+impl Amount {
+
+    /// `true` for amounts that depend on what is available (`all`, `50%`)
+    pub fn is_relative(&self) -> bool {
+        !matches!(self, Amount::Unom(_))
+    }
+
+    /// The amount in unom, with relative amounts taken from `available`
+    pub fn resolve(&self, available: u64) -> u64 {
+        match self {
+            Amount::Unom(n) => *n,
+            Amount::All => available,
+            Amount::Percent(ppm) => {
+                (available as u128 * *ppm as u128 / UNOM_PER_NOM as u128) as u64
+            },
+        }
+    }
+
+    /// The amount in unom, for places where there is nothing for `all` or `50%` to refer to
+    pub fn unom(&self) -> Result<u64> {
+        match self {
+            Amount::Unom(n) => Ok(*n),
+            _ => Err(eyre!("An absolute amount is required here, not '{}'", self)),
+        }
+    }
+}
+
+impl FromStr for Amount {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_lowercase();
+
+        if value == "all" || value == "max" {
+            return Ok(Amount::All);
+        }
+
+        let parsed = if let Some(percent) = value.strip_suffix('%') {
+            // 4 decimal places of a percent are parts per million
+            parse_decimal(percent.trim(), 4).and_then(|ppm| {
+                if ppm == 0 || ppm > UNOM_PER_NOM {
+                    Err(eyre!("Percentage must be greater than 0 and at most 100"))
+                } else {
+                    Ok(Amount::Percent(ppm))
+                }
+            })
+        } else if let Some(unom) = value.strip_suffix("unom") {
+            parse_decimal(unom.trim(), 0).map(Amount::Unom)
+        } else {
+            let nom = value.strip_suffix("nom").unwrap_or(&value);
+            parse_decimal(nom.trim(), 6).map(Amount::Unom)
+        };
+
+        parsed.map_err(|e| format!("Invalid amount '{}': {}", value, e))
+    }
+}
+
+impl std::fmt::Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Amount::Unom(n) => write!(f, "{}",
+                NumberDisplay::new(*n).scale(6).decimal_places(6).trim(true).format()
+            ),
+            Amount::All => write!(f, "all"),
+            Amount::Percent(ppm) => write!(f, "{}%",
+                NumberDisplay::new(*ppm).scale(4).decimal_places(4).trim(true).format()
+            ),
+        }
+    }
+}
+
+/// clap value parser for amounts that must be greater than 0
+pub fn validate_amount(value: &str) -> Result<Amount, String> {
+    match value.parse::<Amount>()? {
+        Amount::Unom(0) => Err(String::from("Amount must be greater than 0")),
+        amount => Ok(amount),
+    }
+}
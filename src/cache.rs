@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use crate::global::PROFILES_DIR;
+use eyre::Result;
+use log::warn;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// A value with the time it was fetched
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Cached<T> {
+    pub timestamp: DateTime<Utc>,
+    pub value:     T,
+}
+
+impl<T> Cached<T> {
+    /// Younger than `ttl` seconds
+    pub fn is_fresh(&self, ttl: u64) -> bool {
+        Utc::now().signed_duration_since(self.timestamp).num_seconds() < ttl.min(i64::MAX as u64) as i64
+    }
+}
+
+/// Last fetched chain data is kept as JSON in `PROFILES_DIR/cache`
+pub fn path(key: &str) -> PathBuf {
+    PROFILES_DIR.join("cache").join(format!("{}.json", key))
+}
+
+/// The cached value for `key`, `None` if there is none or it can't be read
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<Cached<T>> {
+    let path = path(key);
+    let json = fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&json) {
+        Ok(cached) => Some(cached),
+        Err(e) => {
+            warn!("Ignoring unreadable cache file {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Failures are only logged, the value was fetched fine
+pub fn store<T: Serialize>(key: &str, value: &T) {
+    let path = path(key);
+    if let Err(e) = write(&path, value) {
+        warn!("Failed to write cache file {:?}: {}", path, e);
+    }
+}
+
+fn write<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let cached = Cached { timestamp: Utc::now(), value };
+    fs::write(path, serde_json::to_string(&cached)?)?;
+    Ok(())
+}
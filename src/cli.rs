@@ -1,5 +1,7 @@
 use clap::Parser;
 use clap::Subcommand;
+use crate::addressbook;
+use crate::aliases;
 use crate::global;
 use crate::journal;
 use crate::nonce;
@@ -14,6 +16,14 @@ use fmt;
 #[command(version, about, long_about = None)]
 #[command(propagate_version = true)]
 pub struct Cli {
+    /// Only use cached validators, balances and delegations, never run nomic
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Fetch fresh validators even if the cache has not expired
+    #[arg(long, global = true, conflicts_with = "offline")]
+    pub refresh: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -24,6 +34,12 @@ pub enum Commands {
     #[command(visible_alias = "ad", aliases = ["add", "addr", "addre", "addres"])]
     Address(profiles::cli::address::Command),
 
+    #[command(visible_alias = "ab", aliases = ["book", "addressbook"])]
+    AddressBook(addressbook::Cli),
+
+    #[command(visible_alias = "al", aliases = ["alia", "aliases"])]
+    Alias(aliases::Cli),
+
     #[command( visible_alias = "au", aliases = ["aut", "autod"])]
     AutoDelegate(profiles::cli::auto::Command),
 
@@ -31,9 +47,15 @@ pub enum Commands {
     #[command( visible_alias = "b", aliases = ["ba", "bal", "bala", "balan", "balanc"])]
     Balance(profiles::cli::balance::Command),
 
+    #[command(visible_alias = "ca", aliases = ["cal"])]
+    Calc(profiles::cli::calc::Command),
+
     #[command(visible_alias = "cl", aliases = ["cla", "clai"])]
     Claim(profiles::cli::claim::Command),
 
+    #[command(visible_alias = "cm", aliases = ["com", "comp", "compo", "compou", "compoun"])]
+    Compound(profiles::cli::compound::Command),
+
     #[command( visible_alias = "co", aliases = ["con", "conf", "confi"])]
     Config(profiles::cli::config::Cli),
 
@@ -66,12 +88,18 @@ pub enum Commands {
     #[command(visible_alias = "last", aliases = ["lj", "lastj"])]
     LastJournal(journal::cli::LastJournal),
 
+    #[command(visible_alias = "mx", aliases = ["mat", "matr", "matri"])]
+    Matrix(profiles::cli::matrix::Command),
+
     #[command(visible_alias = "n", aliases = ["nom", "nomi"])]
     Nomic(profiles::cli::nomic::Command),
 
     #[command(aliases = ["non", "nonc"])]
     Nonce(nonce::Cli),
 
+    #[command(visible_alias = "pf", aliases = ["port", "portf", "portfo", "portfol", "portfoli"])]
+    Portfolio(profiles::cli::portfolio::Command),
+
     #[command(visible_alias = "p", aliases = ["pr", "pro", "prof", "profi", "profil", "profile"])]
     Profiles(profiles::cli::profiles::Command),
 
@@ -86,6 +114,9 @@ pub enum Commands {
     #[command(visible_alias = "st", aliases = ["sta", "stat"])]
     Stats(profiles::cli::stats::Command),
 
+    #[command(visible_alias = "sw", aliases = ["swe", "swee"])]
+    Sweep(profiles::cli::sweep::Command),
+
     #[command(visible_alias = "v", 
         aliases = ["va", "val", "vali", "valid", "valida", "validat", "validato", "validator"]
     )]
@@ -96,11 +127,16 @@ pub enum Commands {
 
 impl Cli {
     pub fn run(&self) -> Result<()> {
+        global::Runtime::init(self.offline, self.refresh);
         match &self.command {
             Commands::Address(cmd)      => cmd.run(),
+            Commands::AddressBook(cli)  => cli.run(),
+            Commands::Alias(cli)        => cli.run(),
             Commands::AutoDelegate(cmd) => cmd.run(),
             Commands::Balance(cmd)      => cmd.run(),
+            Commands::Calc(cmd)         => cmd.run(),
             Commands::Claim(cmd)        => cmd.run(),
+            Commands::Compound(cmd)     => cmd.run(),
             Commands::Config(cli)       => cli.run(),
             Commands::Delegate(cmd)     => cmd.run(),
             Commands::Delegations(cmd)  => cmd.run(),
@@ -112,12 +148,15 @@ impl Cli {
             Commands::Key(cli)          => cli.run(),
             Commands::LastJournal(cmd)  => cmd.run(),
             Commands::Journalctl(cmd)   => cmd.run(),
+            Commands::Matrix(cmd)       => cmd.run(),
             Commands::Nomic(cmd)        => cmd.run(),
             Commands::Nonce(cli)        => cli.run(),
+            Commands::Portfolio(cmd)    => cmd.run(),
             Commands::Profiles(cmd)     => cmd.run(),
             Commands::Redelegate(cmd)   => cmd.run(),
             Commands::Send(cmd)         => cmd.run(),
             Commands::Stats(cmd)        => cmd.run(),
+            Commands::Sweep(cmd)        => cmd.run(),
             Commands::Validators(cli)   => cli.run(),
             Commands::Z(cli)            => cli.run(),
         }
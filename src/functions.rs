@@ -1,6 +1,7 @@
 //use dirs::home_dir;
+use cosmrs::AccountId;
+use eyre::eyre;
 use eyre::Result;
-use regex::Regex;
 use std::io::{self, Read, Write};
 use std::str::FromStr;
 use num_format::{Locale, ToFormattedString};
@@ -529,20 +530,60 @@ impl TaskStatus {
 }
 
 
-/// Validates whether a given string is a valid Nomic Bech32 address.
+/// Decodes a Bech32 address, verifying its checksum and that its prefix is one of `prefixes`.
+///
+/// # Parameters
+/// - `address`: The address to validate.
+/// - `prefixes`: Accepted human readable parts, e.g. `["nomic"]`.
 ///
-/// This function checks if the provided address starts with `nomic1` 
-/// and is followed by exactly 38 alphanumeric lowercase characters (a-z, 0-9).
-/// The address is converted to lowercase before validation to ensure case insensitivity.
+/// # Returns
+/// - `Ok(String)`: The address in its canonical (lowercase) form.
+/// - `Err(eyre::Error)`: A message saying whether the encoding, the checksum or the prefix is wrong.
+pub fn validate_bech32_address(address: &str, prefixes: &[&str]) -> Result<String> {
+    let address = address.trim();
+    let account_id = AccountId::from_str(address).map_err(|e| eyre!(
+        "Invalid address '{}': not valid Bech32, check it for typos ({})", address, e
+    ))?;
+
+    if !prefixes.contains(&account_id.prefix()) {
+        return Err(eyre!(
+            "Invalid address '{}': prefix '{}' is not one of: {}",
+            address,
+            account_id.prefix(),
+            prefixes.join(", "),
+        ));
+    }
+
+    Ok(account_id.to_string())
+}
+
+/// Validates a Nomic account address (`nomic1...`), including its Bech32 checksum.
+pub fn validate_nomic_address(address: &str) -> Result<String> {
+    validate_bech32_address(address, &["nomic"])
+}
+
+/// Validates a validator address, either an account (`nomic1...`)
+/// or an operator (`nomicvaloper1...`) address, including its Bech32 checksum.
+pub fn validate_validator_address(address: &str) -> Result<String> {
+    validate_bech32_address(address, &["nomic", "nomicvaloper"])
+}
+
+/// `true` if `value` is meant to be an address rather than a name or moniker,
+/// so a failed lookup should report the address error.
+pub fn looks_like_nomic_address(value: &str) -> bool {
+    let value = value.trim().to_lowercase();
+    value.starts_with("nomic1") || value.starts_with("nomicvaloper1")
+}
+
+/// Validates whether a given string is a valid Nomic Bech32 address.
 ///
 /// # Parameters
 /// - `address`: A string slice representing the address to validate.
 ///
 /// # Returns
-/// - `true` if the address is valid according to the specified pattern; otherwise, `false`.
+/// - `true` if the address decodes with a valid checksum and the `nomic` prefix; otherwise, `false`.
 pub fn is_valid_nomic_address(address: &str) -> bool {
-    let re = Regex::new(r"^nomic1[a-z0-9]{38}$").unwrap();
-    re.is_match(&address.to_lowercase())
+    validate_nomic_address(address).is_ok()
 }
 
 pub fn validate_positive<T>(value: &str) -> Result<T, String>
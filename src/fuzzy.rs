@@ -0,0 +1,112 @@
+use eyre::eyre;
+use eyre::Result;
+use std::io::{self, IsTerminal, Write};
+
+/// Edit distance between `a` and `b`, counted in chars
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// How well `candidate` matches `query`, case-insensitive, lower is better:
+/// 0 exact, 1 prefix, 2 substring, 3 + edit distance for near misses, `None` otherwise
+pub fn score(candidate: &str, query: &str) -> Option<usize> {
+    let candidate = candidate.to_lowercase();
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    if candidate == query {
+        return Some(0);
+    }
+    if candidate.starts_with(&query) {
+        return Some(1);
+    }
+    if candidate.contains(&query) {
+        return Some(2);
+    }
+
+    // A typo in the first few characters should still find the candidate
+    let query_len = query.chars().count();
+    let head: String = candidate.chars().take(query_len).collect();
+    let distance = levenshtein(&candidate, &query).min(levenshtein(&head, &query));
+    let tolerance = (query_len / 3).max(1);
+    (distance <= tolerance).then_some(3 + distance)
+}
+
+/// Indexes of the `candidates` that match `query`, best first
+pub fn rank<S: AsRef<str>>(candidates: &[S], query: &str) -> Vec<usize> {
+    let mut scored: Vec<(usize, usize)> = candidates.iter()
+        .enumerate()
+        .filter_map(|(index, candidate)| score(candidate.as_ref(), query).map(|score| (score, index)))
+        .collect();
+    scored.sort();
+    scored.into_iter().map(|(_, index)| index).collect()
+}
+
+/// Up to `limit` candidates closest to `query` by edit distance, for "did you mean"
+pub fn suggest<S: AsRef<str>>(candidates: &[S], query: &str, limit: usize) -> Vec<String> {
+    let query = query.to_lowercase();
+    let tolerance = (query.chars().count() / 2).max(2);
+    let mut scored: Vec<(usize, &str)> = candidates.iter()
+        .map(|candidate| (levenshtein(&candidate.as_ref().to_lowercase(), &query), candidate.as_ref()))
+        .filter(|(distance, _)| *distance <= tolerance)
+        .collect();
+    scored.sort();
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored.into_iter().take(limit).map(|(_, candidate)| candidate.to_string()).collect()
+}
+
+/// ` Did you mean a, b?` or an empty string
+pub fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(" Did you mean {}?", suggestions.join(", "))
+    }
+}
+
+/// Asks which of `options` was meant by `query`. Only prompts when attached to a terminal,
+/// `None` when not interactive or nothing was chosen.
+pub fn choose(what: &str, query: &str, options: &[String]) -> Result<Option<usize>> {
+    if options.is_empty() || !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Ok(None);
+    }
+
+    eprintln!("No exact {} match for '{}', did you mean:", what, query);
+    for (index, option) in options.iter().enumerate() {
+        eprintln!("  {:>2}) {}", index + 1, option);
+    }
+    eprint!("Choose 1-{} or press Enter to cancel: ", options.len());
+    io::stderr().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().parse::<usize>().ok()
+        .filter(|choice| (1..=options.len()).contains(choice))
+        .map(|choice| choice - 1))
+}
+
+/// Confirms which of the near misses in `options` was meant by `query`. Even a single
+/// near miss is only taken when chosen on a terminal, so a typo can't silently pick a
+/// different profile or validator. Otherwise fails suggesting the options.
+pub fn confirm(what: &str, query: &str, options: &[String]) -> Result<usize> {
+    match choose(what, query, options)? {
+        Some(choice) => Ok(choice),
+        None => Err(eyre!(
+            "No exact {} match for `{}`.{}",
+            what, query, did_you_mean(&options[..options.len().min(5)]),
+        )),
+    }
+}
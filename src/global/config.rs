@@ -73,15 +73,102 @@ impl Default for JournalctlSummaryMoniker {
     }
 }
 
+/// What to do with a delegation that would breach the exposure cap
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExposureAction {
+    /// Don't delegate
+    #[default]
+    Skip,
+    /// Delegate to the next validator in the profile's list that stays within the cap
+    Divert,
+}
+
+/// Limits on the total stake, across all profiles, with a single validator
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ExposureCap {
+    /// Maximum unom with one validator, 0 = no limit
+    #[serde(default)]
+    pub absolute: u64,
+    /// Maximum share (ppm) of the fleet's total stake with one validator, 0 = no limit.
+    /// Only enforced once the fleet has stake with enough validators to meet it.
+    #[serde(default)]
+    pub percent: u64,
+    #[serde(default)]
+    pub action: ExposureAction,
+}
+
+impl ExposureCap {
+    pub fn is_enabled(&self) -> bool {
+        self.absolute > 0 || self.percent > 0
+    }
+}
+
+fn default_snapshot_retention() -> u64 {
+    30
+}
+
+fn default_validator_cache_ttl() -> u64 {
+    300
+}
+
+fn default_address_prefixes() -> Vec<String> {
+    ["nomic", "cosmos", "osmo"].iter().map(|prefix| prefix.to_string()).collect()
+}
+
+/// Trimmed and lowercase, without duplicates, empty entries or ones no address can have
+fn normalize_address_prefixes(prefixes: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for prefix in prefixes {
+        let prefix = prefix.trim().to_lowercase();
+        if prefix.is_empty() || normalized.contains(&prefix) {
+            continue;
+        }
+        // Addresses are only made for lowercase alphanumeric prefixes
+        if !prefix.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()) {
+            warn!("Ignoring invalid address prefix {:?}", prefix);
+            continue;
+        }
+        normalized.push(prefix);
+    }
+    normalized
+}
+
+fn default_chain_id() -> String {
+    "nomic-stakenet-3".to_string()
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct GlobalConfig {
     pub minimum_balance: u64,
     pub minimum_balance_ratio: u64,
     pub minimum_stake: u64,
     pub adjust_minimum_stake: bool,
+    #[serde(default)]
+    pub optimize_minimum_stake: bool,
     pub minimum_stake_rounding: u64,
     pub claim_fee: u64,
     pub stake_fee: u64,
+    /// Warn when our combined stake exceeds this share of a validator's voting power (ppm, 0 = off)
+    #[serde(default)]
+    pub validator_share_cap: u64,
+    #[serde(default)]
+    pub exposure_cap: ExposureCap,
+    /// Days to keep validator set snapshots, 0 = don't save snapshots
+    #[serde(default = "default_snapshot_retention")]
+    pub snapshot_retention: u64,
+    /// Seconds to reuse cached validators before running `nomic validators` again
+    #[serde(default = "default_validator_cache_ttl")]
+    pub validator_cache_ttl: u64,
+    /// Shell command that prints the passphrase for encrypted keys, used when KEY_PASSPHRASE is unset
+    #[serde(default)]
+    pub key_passphrase_command: Option<String>,
+    /// Bech32 prefixes for linked addresses on other chains, e.g. for IBC
+    #[serde(default = "default_address_prefixes")]
+    pub address_prefixes: Vec<String>,
+    /// Chain ID for transactions signed offline
+    #[serde(default = "default_chain_id")]
+    pub chain_id: String,
     pub nomic_legacy_version: Option<String>,
     pub nomic_exe: PathBuf,
     pub journalctl: JournalctlConfig,
@@ -96,9 +183,17 @@ impl Default for GlobalConfig {
             minimum_balance_ratio: 1_000,
             minimum_stake: 1_000_000,
             adjust_minimum_stake: false,
+            optimize_minimum_stake: false,
             minimum_stake_rounding: 100_000,
             claim_fee: 10_000,
             stake_fee: 10_000,
+            validator_share_cap: 0,
+            exposure_cap: ExposureCap::default(),
+            snapshot_retention: default_snapshot_retention(),
+            validator_cache_ttl: default_validator_cache_ttl(),
+            key_passphrase_command: None,
+            address_prefixes: default_address_prefixes(),
+            chain_id: default_chain_id(),
             nomic_legacy_version: Some("".to_string()),
             nomic_exe: PathBuf::from("/usr/local/bin/nomic"),
             journalctl: JournalctlConfig {
@@ -165,6 +260,9 @@ impl GlobalConfig {
         if let Ok(val) = env::var("ADJUST_MINIMUM_STAKE") {
             config.adjust_minimum_stake = val.parse().unwrap_or(config.adjust_minimum_stake);
         }
+        if let Ok(val) = env::var("OPTIMIZE_MINIMUM_STAKE") {
+            config.optimize_minimum_stake = val.parse().unwrap_or(config.optimize_minimum_stake);
+        }
         if let Ok(val) = env::var("MINIMUM_STAKE_ROUNDING") {
             config.minimum_stake_rounding = val.parse().unwrap_or(config.minimum_stake_rounding);
         }
@@ -174,6 +272,38 @@ impl GlobalConfig {
         if let Ok(val) = env::var("STAKE_FEE") {
             config.stake_fee = val.parse().unwrap_or(config.stake_fee);
         }
+        if let Ok(val) = env::var("VALIDATOR_SHARE_CAP") {
+            config.validator_share_cap = val.parse().unwrap_or(config.validator_share_cap);
+        }
+        if let Ok(val) = env::var("EXPOSURE_CAP_ABSOLUTE") {
+            config.exposure_cap.absolute = val.parse().unwrap_or(config.exposure_cap.absolute);
+        }
+        if let Ok(val) = env::var("EXPOSURE_CAP_PERCENT") {
+            config.exposure_cap.percent = val.parse().unwrap_or(config.exposure_cap.percent);
+        }
+        if let Ok(val) = env::var("EXPOSURE_CAP_ACTION") {
+            match val.to_lowercase().as_str() {
+                "skip"   => config.exposure_cap.action = ExposureAction::Skip,
+                "divert" => config.exposure_cap.action = ExposureAction::Divert,
+                _ => warn!("Invalid EXPOSURE_CAP_ACTION: {}", val),
+            }
+        }
+        if let Ok(val) = env::var("SNAPSHOT_RETENTION") {
+            config.snapshot_retention = val.parse().unwrap_or(config.snapshot_retention);
+        }
+        if let Ok(val) = env::var("VALIDATOR_CACHE_TTL") {
+            config.validator_cache_ttl = val.parse().unwrap_or(config.validator_cache_ttl);
+        }
+        if let Ok(val) = env::var("KEY_PASSPHRASE_COMMAND") {
+            config.key_passphrase_command = Some(val);
+        }
+        if let Ok(val) = env::var("ADDRESS_PREFIXES") {
+            config.address_prefixes = val.split(',').map(String::from).collect();
+        }
+        config.address_prefixes = normalize_address_prefixes(&config.address_prefixes);
+        if let Ok(val) = env::var("CHAIN_ID") {
+            config.chain_id = val;
+        }
         if let Ok(val) = env::var("NOMIC_LEGACY_VERSION") {
             config.nomic_legacy_version = Some(val);
         }
@@ -279,3 +409,17 @@ lazy_static! {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_prefixes_are_normalized() {
+        let prefixes: Vec<String> = [" Osmo", "cosmos", "", "COSMOS", "os mo", "osmo-1", "é", "nomic"]
+            .iter()
+            .map(|prefix| prefix.to_string())
+            .collect();
+        assert_eq!(normalize_address_prefixes(&prefixes), ["osmo", "cosmos", "nomic"]);
+    }
+}
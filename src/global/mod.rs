@@ -1,9 +1,13 @@
 mod config;
 mod cli;
+mod runtime;
 
 pub use config::GroupBy;
 pub use config::CONFIG;
+pub use config::ExposureAction;
+pub use config::ExposureCap;
 pub use config::PROFILES_DIR;
+pub use runtime::Runtime;
 pub use cli::Cli;
 // pub use config::GlobalConfig;
 
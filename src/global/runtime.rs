@@ -0,0 +1,38 @@
+use eyre::eyre;
+use eyre::Result;
+use once_cell::sync::OnceCell;
+
+static RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+/// Command line flags that apply to every command
+#[derive(Clone, Debug, Default)]
+pub struct Runtime {
+    /// Only use cached chain data, never run nomic
+    pub offline: bool,
+    /// Ignore the cache TTL and fetch fresh data
+    pub refresh: bool,
+}
+
+impl Runtime {
+
+    /// Sets the flags for this run, only the first call has any effect
+    pub fn init(offline: bool, refresh: bool) {
+        let _ = RUNTIME.set(Self { offline, refresh });
+    }
+
+    pub fn offline() -> bool {
+        RUNTIME.get().is_some_and(|runtime| runtime.offline)
+    }
+
+    pub fn refresh() -> bool {
+        RUNTIME.get().is_some_and(|runtime| runtime.refresh)
+    }
+
+    /// Errors in offline mode, for anything that has to reach the chain
+    pub fn ensure_online(action: &str) -> Result<()> {
+        if Self::offline() {
+            return Err(eyre!("Cannot {} in offline mode", action));
+        }
+        Ok(())
+    }
+}
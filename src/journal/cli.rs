@@ -36,6 +36,10 @@ pub enum JournalctlCommands {
 
         #[arg(long, short, action = clap::ArgAction::SetTrue)]
         follow: bool,
+
+        /// Only profiles with this tag
+        #[arg(long, short, visible_alias = "group")]
+        tag: Option<String>,
     },
 }
 
@@ -61,8 +65,16 @@ pub struct Journalctl {
 impl Journalctl {
     pub fn run(&self) -> Result<()> {
         match &self.subcommand {
-            Some(JournalctlCommands::Summary { group_by, follow }) => {
-                summary(group_by.clone(), *follow)
+            Some(JournalctlCommands::Summary { group_by, follow, tag }) => {
+                let profiles = match tag {
+                    Some(tag) => {
+                        let mut collection = ProfileCollection::new()?;
+                        collection.filter_tag(tag)?;
+                        Some(collection.list_names()?)
+                    },
+                    None => None,
+                };
+                summary(group_by.clone(), *follow, profiles)
             }
             None => {
                 let staked_or_not = if self.staked {
@@ -240,7 +240,8 @@ impl std::fmt::Display for Summary { // Fully qualified fmt
     }
 }
 
-pub fn summary(group_by: GroupBy, follow: bool) -> Result<()> {
+/// Summarise staking by profile or moniker, limited to `profiles` (names) if given
+pub fn summary(group_by: GroupBy, follow: bool, profiles: Option<Vec<String>>) -> Result<()> {
     // Define the grep expression for staked status
     let grep_expr = r#"{[^}]*"staked"[[:space:]]*:[[:space:]]*"✅"[^}]*}"#;
 
@@ -251,6 +252,14 @@ pub fn summary(group_by: GroupBy, follow: bool) -> Result<()> {
     // Call the common function with the grep expression and specific line processing
     process_journal_lines(grep_expr, follow, |line| {
         let journal = Journal::from_json_str(&line)?;
+
+        if let Some(profiles) = &profiles {
+            match journal.get::<String>("profile") {
+                Some(profile) if profiles.contains(&profile) => {},
+                _ => return Ok(()),
+            }
+        }
+
         let timestamp = journal.get::<DateTime<Utc>>("timestamp");
 
         // Determine the group based on the GroupBy enum
@@ -1,5 +1,10 @@
+mod addressbook;
+mod aliases;
+mod amount;
+mod cache;
 mod cli;
 mod functions;
+mod fuzzy;
 mod privkey;
 mod nonce;
 mod profiles;
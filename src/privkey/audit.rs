@@ -0,0 +1,45 @@
+use chrono::Utc;
+use crate::global::PROFILES_DIR;
+use eyre::Result;
+use eyre::WrapErr;
+use log::warn;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// One line of the audit log
+#[derive(Debug, Serialize)]
+struct Event<'a> {
+    time:    String,
+    event:   &'a str,
+    subject: &'a str,
+    detail:  &'a str,
+}
+
+/// Where events touching key material are appended, one JSON object per line
+pub fn path() -> PathBuf {
+    PROFILES_DIR.join("audit.log")
+}
+
+fn append(event: &str, subject: &str, detail: &str) -> Result<()> {
+    let line = serde_json::to_string(&Event {
+        time: Utc::now().to_rfc3339(),
+        event,
+        subject,
+        detail,
+    })?;
+    let path = path();
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)
+        .wrap_err_with(|| format!("Failed to open audit log {:?}", path))?;
+    writeln!(file, "{}", line)
+        .wrap_err_with(|| format!("Failed to write audit log {:?}", path))
+}
+
+/// Records an event, e.g. an export of `subject` (an address or key file).
+/// Failures are only logged.
+pub fn record(event: &str, subject: &str, detail: &str) {
+    if let Err(e) = append(event, subject, detail) {
+        warn!("{}", e);
+    }
+}
@@ -1,9 +1,25 @@
 
 use clap::{Parser, Subcommand};
+use crate::addressbook::AddressBook;
+use crate::aliases::ValidatorAliases;
+use crate::amount::{Amount, validate_amount};
+use crate::functions::{validate_nomic_address, validate_validator_address};
+use crate::global::CONFIG;
+use crate::privkey::audit;
+use crate::privkey::encrypted;
+use crate::privkey::ExportArgs;
+use crate::privkey::mnemonic;
+use crate::privkey::HdPath;
 use crate::privkey::PrivKey;
+use crate::privkey::Share;
+use crate::privkey::SignedMessage;
+use crate::privkey::sign::OutputFormat as SignOutputFormat;
+use crate::privkey::{SignedTx, TxMessage, TxParams};
+use crate::profiles::ProfileCollection;
 use eyre::{eyre, Result};
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Read, Write};
 use std::path::PathBuf;
+use zeroize::Zeroizing;
 
 /// Defines the CLI structure for the `privkey` command.
 #[derive(Parser)]
@@ -40,6 +56,269 @@ pub struct InputArgs {
 
 }
 
+#[derive(Parser, Debug)]
+pub struct HdPathArgs {
+    /// Account number, the `{account}` in the derivation path
+    #[arg(long, default_value_t = 0)]
+    pub account: u32,
+
+    /// Address index, the `{index}` in the derivation path
+    #[arg(long, default_value_t = 0)]
+    pub index: u32,
+
+    /// Derivation path, `{account}` and `{index}` are filled in
+    #[arg(long, default_value = mnemonic::DEFAULT_HD_PATH)]
+    pub hd_path: String,
+
+    /// Prompt for a BIP39 passphrase, Keplr does not use one
+    #[arg(long)]
+    pub bip39_passphrase: bool,
+}
+
+impl HdPathArgs {
+    pub fn hd_path(&self) -> HdPath {
+        HdPath::new(Some(&self.hd_path), self.account, self.index)
+    }
+
+    pub fn password(&self) -> Result<String> {
+        if !self.bip39_passphrase {
+            return Ok(String::new());
+        }
+        if !io::stdin().is_terminal() {
+            return Err(eyre!("No BIP39 passphrase: it is only prompted for, run in a terminal"));
+        }
+        let term = console::Term::stderr();
+        term.write_str("BIP39 passphrase: ")?;
+        Ok(term.read_secure_line()?)
+    }
+}
+
+#[derive(Subcommand)]
+pub enum MnemonicCommand {
+    /// Generate a new 24 word mnemonic
+    #[command(visible_alias = "gen")]
+    Generate {
+        #[command(flatten)]
+        hd_path_args: HdPathArgs,
+    },
+
+    /// Check the words and checksum of a mnemonic
+    Validate {
+        /// File holding the mnemonic, piped or prompted for if omitted. A phrase given
+        /// here is kept in shell history and visible to other users in `ps`
+        phrase: Option<String>,
+    },
+
+    /// Show the address a mnemonic derives
+    #[command(visible_alias = "ad")]
+    Address {
+        /// File holding the mnemonic, piped or prompted for if omitted. A phrase given
+        /// here is kept in shell history and visible to other users in `ps`
+        phrase: Option<String>,
+
+        #[command(flatten)]
+        hd_path_args: HdPathArgs,
+    },
+
+    /// Show the mnemonic kept with a key by `import --mnemonic --keep-mnemonic`
+    Export {
+        #[command(flatten)]
+        key_file: KeyFileArgs,
+
+        #[command(flatten)]
+        hd_path_args: HdPathArgs,
+
+        #[command(flatten)]
+        export_args: ExportArgs,
+    },
+}
+
+impl MnemonicCommand {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            MnemonicCommand::Generate { hd_path_args } => {
+                let phrase = mnemonic::generate();
+                let privkey = PrivKey::from_mnemonic(&phrase, &hd_path_args.password()?, &hd_path_args.hd_path())?;
+                println!("{}", phrase);
+                eprintln!("Address: {} ({})", privkey.address()?, hd_path_args.hd_path());
+                eprintln!("Warning: Write the mnemonic down and keep it offline, it controls the funds.");
+                Ok(())
+            },
+            MnemonicCommand::Validate { phrase } => {
+                mnemonic::validate(&mnemonic::read(phrase.as_deref())?)?;
+                println!("Valid mnemonic");
+                Ok(())
+            },
+            MnemonicCommand::Address { phrase, hd_path_args } => {
+                let phrase = mnemonic::read(phrase.as_deref())?;
+                let privkey = PrivKey::from_mnemonic(&phrase, &hd_path_args.password()?, &hd_path_args.hd_path())?;
+                println!("{}", privkey.address()?);
+                Ok(())
+            },
+            MnemonicCommand::Export { key_file, hd_path_args, export_args } => {
+                let path = key_file.path()?;
+                let phrase = Zeroizing::new(mnemonic::load(&path, &hd_path_args.password()?, &hd_path_args.hd_path())?);
+                export_args.confirm("mnemonic", &path.to_string_lossy())?;
+                println!("{}", phrase.as_str());
+                Ok(())
+            },
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct SignArgs {
+    /// Profile name, address or home holding the key, the default home if omitted
+    #[arg(long, short)]
+    pub profile: Option<String>,
+
+    /// Key file path
+    #[arg(long, conflicts_with = "profile")]
+    pub file: Option<PathBuf>,
+
+    /// Chain ID, the global config chain_id if omitted
+    #[arg(long)]
+    pub chain_id: Option<String>,
+
+    /// Account number of the signer on chain
+    #[arg(long, short)]
+    pub account_number: u64,
+
+    /// Account sequence (nonce) for this transaction
+    #[arg(long, short)]
+    pub sequence: u64,
+
+    /// Fee in unom
+    #[arg(long, default_value_t = 0)]
+    pub fee: u64,
+
+    /// Gas limit
+    #[arg(long, default_value_t = 200_000)]
+    pub gas_limit: u64,
+
+    /// Transaction memo
+    #[arg(long, default_value = "")]
+    pub memo: String,
+
+    /// Output format
+    #[arg(long, short)]
+    pub format: Option<SignOutputFormat>,
+}
+
+impl SignArgs {
+    fn sign(&self, messages: Vec<TxMessage>) -> Result<()> {
+        let key_file = KeyFileArgs { profile: self.profile.clone(), file: self.file.clone() };
+        let privkey = PrivKey::load(key_file.path()?, false)?;
+        let params = TxParams {
+            chain_id:       self.chain_id.clone().unwrap_or_else(|| CONFIG.chain_id.clone()),
+            account_number: self.account_number,
+            sequence:       self.sequence,
+            fee:            self.fee,
+            gas_limit:      self.gas_limit,
+            memo:           self.memo.clone(),
+        };
+        SignedTx::sign(&privkey, messages, params)?.print(self.format.clone())
+    }
+}
+
+/// A validator address or global alias
+fn resolve_validator(validator: &str) -> Result<String> {
+    let address = ValidatorAliases::shared().resolve(validator).unwrap_or(validator);
+    validate_validator_address(address)
+}
+
+#[derive(Subcommand)]
+pub enum SignCommand {
+    /// Sign a delegation
+    #[command(visible_alias = "de")]
+    Delegate {
+        /// Validator address or alias
+        validator: String,
+
+        /// Amount to delegate (e.g. 1.5, 1500000unom)
+        #[arg(value_parser = validate_amount)]
+        amount: Amount,
+
+        #[command(flatten)]
+        sign_args: SignArgs,
+    },
+
+    /// Sign a reward claim from one or more validators
+    #[command(visible_alias = "cl")]
+    Claim {
+        /// Validator addresses or aliases
+        #[arg(required = true)]
+        validators: Vec<String>,
+
+        #[command(flatten)]
+        sign_args: SignArgs,
+    },
+
+    /// Sign a transfer
+    #[command(visible_alias = "se")]
+    Send {
+        /// Destination address or address book label
+        to: String,
+
+        /// Amount to send (e.g. 1.5, 1500000unom)
+        #[arg(value_parser = validate_amount)]
+        amount: Amount,
+
+        #[command(flatten)]
+        sign_args: SignArgs,
+    },
+}
+
+impl SignCommand {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            SignCommand::Delegate { validator, amount, sign_args } => {
+                sign_args.sign(vec![TxMessage::Delegate {
+                    validator: resolve_validator(validator)?,
+                    amount:    amount.unom()?,
+                }])
+            },
+            SignCommand::Claim { validators, sign_args } => {
+                let validators = validators.iter()
+                    .map(|validator| resolve_validator(validator))
+                    .collect::<Result<Vec<_>>>()?;
+                sign_args.sign(vec![TxMessage::Claim { validators }])
+            },
+            SignCommand::Send { to, amount, sign_args } => {
+                let book = AddressBook::load()?;
+                let to = match book.get(to) {
+                    Some(entry) => entry.address.clone(),
+                    None => validate_nomic_address(to)?,
+                };
+                sign_args.sign(vec![TxMessage::Send { to, amount: amount.unom()? }])
+            },
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct KeyFileArgs {
+    /// Profile name, address or home, the default home if omitted
+    #[arg(conflicts_with = "file")]
+    pub profile: Option<String>,
+
+    /// Key file path
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+impl KeyFileArgs {
+    fn path(&self) -> Result<PathBuf> {
+        match &self.file {
+            Some(file) => Ok(file.clone()),
+            None => Ok(ProfileCollection::new()?
+                .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?
+                .key_file()?
+                .to_path_buf()),
+        }
+    }
+}
+
 /// Subcommands for the `privkey` command
 #[derive(Subcommand)]
 pub enum Command {
@@ -48,12 +327,124 @@ pub enum Command {
     Address {
         #[command(flatten)]
         input_args: InputArgs,
+
+        /// Bech32 prefix of another chain, e.g. cosmos or osmo
+        #[arg(long, conflicts_with = "all_prefixes")]
+        prefix: Option<String>,
+
+        /// List the address for every prefix in the global address_prefixes
+        #[arg(long, short = 'A')]
+        all_prefixes: bool,
+    },
+
+    /// Rebuild a key from Shamir shares made by split
+    #[command(visible_alias = "co")]
+    Combine {
+        /// Shares or share files, read one per line from stdin if omitted
+        shares: Vec<String>,
+
+        /// Key file to write, the key is printed as hex if omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Overwrite an existing output file
+        #[arg(long, requires = "output")]
+        overwrite: bool,
+
+        #[command(flatten)]
+        export_args: ExportArgs,
+    },
+
+    /// Decrypt a key file and its kept mnemonic in place
+    #[command(visible_alias = "dec")]
+    Decrypt {
+        #[command(flatten)]
+        key_file: KeyFileArgs,
+    },
+
+    /// Encrypt a key file and its kept mnemonic in place with a passphrase
+    #[command(visible_alias = "enc")]
+    Encrypt {
+        #[command(flatten)]
+        key_file: KeyFileArgs,
     },
 
     /// Export Private key caution
     Export {
         #[command(flatten)]
         input_args: InputArgs,
+
+        #[command(flatten)]
+        export_args: ExportArgs,
+    },
+
+    /// Generate, check and derive keys from BIP39 mnemonics
+    #[command(visible_alias = "mn")]
+    Mnemonic {
+        #[command(subcommand)]
+        command: MnemonicCommand,
+    },
+
+    /// Sign a transaction offline for later broadcast
+    Sign {
+        #[command(subcommand)]
+        command: SignCommand,
+    },
+
+    /// Sign a message to prove control of an address (ADR-036)
+    #[command(visible_alias = "sm")]
+    SignMessage {
+        /// Message to sign, read from stdin if omitted
+        #[arg(conflicts_with = "message_file")]
+        message: Option<String>,
+
+        /// Sign the contents of a file
+        #[arg(long)]
+        message_file: Option<PathBuf>,
+
+        /// Profile name, address or home holding the key, the default home if omitted
+        #[arg(long, short)]
+        profile: Option<String>,
+
+        /// Key file path
+        #[arg(long, conflicts_with = "profile")]
+        file: Option<PathBuf>,
+
+        /// Print indented JSON
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Split a key into Shamir shares for cold backup, any threshold of them rebuild it
+    Split {
+        #[command(flatten)]
+        key_file: KeyFileArgs,
+
+        /// Number of shares to make
+        #[arg(long, short = 'n')]
+        shares: u8,
+
+        /// Number of shares needed to rebuild the key
+        #[arg(long, short = 'k')]
+        threshold: u8,
+
+        /// Write each share to a file in this directory instead of printing them
+        #[arg(long, short)]
+        output_dir: Option<PathBuf>,
+
+        #[command(flatten)]
+        export_args: ExportArgs,
+    },
+
+    /// Verify a signature made by sign-message, Keplr or other ADR-036 tools
+    #[command(visible_alias = "vm")]
+    VerifyMessage {
+        /// JSON file with signer, data, pub_key and signature, stdin if omitted
+        signature: Option<PathBuf>,
+
+        /// Also check the signed data is this message
+        #[arg(long, short)]
+        message: Option<String>,
     },
 
     /// Save Private key to file
@@ -85,7 +476,7 @@ impl Cli {
     pub fn run(&self) -> Result<()> {
         match &self.command {
 
-            Some(Command::Address { input_args }) => {
+            Some(Command::Address { input_args, prefix, all_prefixes }) => {
                 let privkey = if input_args.stdin_args.stdin {
                     PrivKey::stdin(
                         input_args.stdin_args.max_attempts, 
@@ -101,10 +492,69 @@ impl Cli {
                         }
                     }
                 };
-                Ok(println!("{}", privkey.address()?))
+                if *all_prefixes {
+                    for (prefix, address) in privkey.addresses_with_prefixes(&CONFIG.address_prefixes)? {
+                        println!("{:<10} {}", prefix, address);
+                    }
+                    return Ok(());
+                }
+                match prefix {
+                    Some(prefix) => Ok(println!("{}", privkey.address_with_prefix(prefix)?)),
+                    None => Ok(println!("{}", privkey.address()?)),
+                }
+            },
+
+            Some(Command::Combine { shares, output, overwrite, export_args }) => {
+                let mut inputs = shares.clone();
+                if inputs.is_empty() {
+                    let mut input = String::new();
+                    io::stdin().read_to_string(&mut input)?;
+                    inputs = input.lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .map(String::from)
+                        .collect();
+                }
+                let shares = inputs.iter()
+                    .map(|input| Share::read(input))
+                    .collect::<Result<Vec<_>>>()?;
+                let privkey = Share::combine(&shares)?;
+                audit::record("combine", privkey.address()?, &format!("{} shares", shares.len()));
+
+                match output {
+                    Some(file) => privkey.save(file, *overwrite),
+                    None => {
+                        export_args.confirm("private key", privkey.address()?)?;
+                        Ok(println!("{}", privkey.export().as_str()))
+                    },
+                }
+            },
+
+            Some(Command::Decrypt { key_file }) => {
+                let path = key_file.path()?;
+                if !encrypted::is_encrypted_file(&path) {
+                    return Err(eyre!("{:?} is not encrypted", path));
+                }
+                let privkey = PrivKey::load(&path, false)?;
+                encrypted::write_private(&path, privkey.bytes())?;
+                mnemonic::reseal(&path, None)?;
+                audit::record("decrypt", privkey.address()?, &path.to_string_lossy());
+                println!("Decrypted {:?}", path);
+                Ok(())
             },
 
-            Some(Command::Export { input_args }) => {
+            Some(Command::Encrypt { key_file }) => {
+                let path = key_file.path()?;
+                if encrypted::is_encrypted_file(&path) {
+                    return Err(eyre!("{:?} is already encrypted", path));
+                }
+                let privkey = PrivKey::load(&path, false)?;
+                let passphrase = encrypted::passphrase("New passphrase", true)?;
+                privkey.save_encrypted(&path, &passphrase, true)?;
+                mnemonic::reseal(&path, Some(&passphrase))
+            },
+
+            Some(Command::Export { input_args, export_args }) => {
                 let privkey = if input_args.stdin_args.stdin {
                     PrivKey::stdin(
                         input_args.stdin_args.max_attempts, 
@@ -120,7 +570,80 @@ impl Cli {
                         }
                     }
                 };
-                Ok(println!("{}", privkey.export()))
+                export_args.confirm("private key", privkey.address()?)?;
+                Ok(println!("{}", privkey.export().as_str()))
+            },
+
+            Some(Command::Mnemonic { command }) => command.run(),
+
+            Some(Command::Sign { command }) => command.run(),
+
+            Some(Command::SignMessage { message, message_file, profile, file, pretty }) => {
+                let data = match (message, message_file) {
+                    (Some(message), _) => message.as_bytes().to_vec(),
+                    (None, Some(path)) => std::fs::read(path)
+                        .map_err(|e| eyre!("Failed to read {:?}: {}", path, e))?,
+                    (None, None) => {
+                        let mut data = Vec::new();
+                        io::stdin().read_to_end(&mut data)?;
+                        data
+                    },
+                };
+                let key_file = KeyFileArgs { profile: profile.clone(), file: file.clone() };
+                let signed = SignedMessage::sign(&PrivKey::load(key_file.path()?, false)?, &data)?;
+                if *pretty {
+                    println!("{}", serde_json::to_string_pretty(&signed)?);
+                } else {
+                    println!("{}", serde_json::to_string(&signed)?);
+                }
+                Ok(())
+            },
+
+            Some(Command::Split { key_file, shares, threshold, output_dir, export_args }) => {
+                let privkey = PrivKey::load(key_file.path()?, false)?;
+                let address = privkey.address()?;
+                let split = Share::split(&privkey, *shares, *threshold)?;
+
+                match output_dir {
+                    Some(dir) => {
+                        std::fs::create_dir_all(dir)
+                            .map_err(|e| eyre!("Failed to create {:?}: {}", dir, e))?;
+                        for share in &split {
+                            println!("{}", share.save(dir)?.display());
+                        }
+                        audit::record("split", address, &format!("{} of {} shares written to {:?}", threshold, shares, dir));
+                    },
+                    None => {
+                        export_args.confirm("key shares", address)?;
+                        for share in &split {
+                            println!("{}", share.encode().as_str());
+                        }
+                    },
+                }
+                eprintln!("Any {} of these {} shares rebuild the key of {}, keep them apart.", threshold, shares, address);
+                Ok(())
+            },
+
+            Some(Command::VerifyMessage { signature, message }) => {
+                let json = match signature {
+                    Some(path) => std::fs::read_to_string(path)
+                        .map_err(|e| eyre!("Failed to read {:?}: {}", path, e))?,
+                    None => {
+                        let mut json = String::new();
+                        io::stdin().read_to_string(&mut json)?;
+                        json
+                    },
+                };
+                let signed: SignedMessage = serde_json::from_str(&json)
+                    .map_err(|e| eyre!("Invalid signature JSON: {}", e))?;
+                signed.verify()?;
+                if let Some(message) = message {
+                    if signed.message()? != message.as_bytes() {
+                        return Err(eyre!("Signature is valid but for a different message"));
+                    }
+                }
+                println!("Valid signature by {}", signed.signer);
+                Ok(())
             },
 
             Some(Command::Write { input, stdin_args, output, force }) => {
@@ -0,0 +1,525 @@
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use crate::global::CONFIG;
+use crate::privkey::PrivKey;
+use eyre::eyre;
+use eyre::Result;
+use eyre::WrapErr;
+use log::warn;
+use rand::RngCore;
+use std::env;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+/// Marks an encrypted key file, a plaintext key is 32 raw bytes or 64 hex characters
+const MAGIC: &[u8; 8] = b"NTKEYv1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+/// Magic, scrypt log_n, r and p, salt and nonce
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4 + 4 + SALT_LEN + NONCE_LEN;
+
+/// scrypt cost, the recommended interactive parameters
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+/// The most a key file may ask for, 1 GiB with r = 8, so a tampered file can't exhaust memory
+const MAX_SCRYPT_LOG_N: u8 = 20;
+
+/// The last passphrase that decrypted a key, so a run over many profiles asks once
+static PASSPHRASE: Mutex<Option<Zeroizing<String>>> = Mutex::new(None);
+
+/// `true` if `data` is in the encrypted key format
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// `true` if the file at `path` holds an encrypted key
+pub fn is_encrypted_file(path: &Path) -> bool {
+    fs::read(path).map(|data| is_encrypted(&data)).unwrap_or(false)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<Zeroizing<[u8; 32]>> {
+    let params = scrypt::Params::new(log_n, r, p, 32)
+        .map_err(|e| eyre!("Invalid scrypt parameters: {}", e))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key[..])
+        .map_err(|e| eyre!("Failed to derive key from passphrase: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypts the key with a scrypt-derived key and ChaCha20-Poly1305, the header is authenticated
+pub fn encrypt(privkey: &PrivKey, passphrase: &str) -> Result<Vec<u8>> {
+    seal(privkey.bytes(), passphrase)
+}
+
+/// Encrypts any secret in the key file format
+pub fn seal(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(MAGIC);
+    header.push(SCRYPT_LOG_N);
+    header.extend_from_slice(&SCRYPT_R.to_be_bytes());
+    header.extend_from_slice(&SCRYPT_P.to_be_bytes());
+    header.extend_from_slice(&salt);
+    header.extend_from_slice(&nonce);
+
+    let key = derive_key(passphrase, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key[..]));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: &header })
+        .map_err(|_| eyre!("Failed to encrypt"))?;
+
+    header.extend_from_slice(&ciphertext);
+    Ok(header)
+}
+
+/// Decrypts an encrypted key, fails on a wrong passphrase or a damaged file
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<PrivKey> {
+    to_privkey(open(data, passphrase)?)
+}
+
+fn to_privkey(plaintext: Zeroizing<Vec<u8>>) -> Result<PrivKey> {
+    let bytes: [u8; 32] = plaintext.as_slice().try_into()
+        .map_err(|_| eyre!("Decrypted key is not 32 bytes"))?;
+    Ok(PrivKey::new(Some(bytes)))
+}
+
+/// Decrypts anything `seal` encrypted, fails on a wrong passphrase or a damaged file
+pub fn open(data: &[u8], passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+    if !is_encrypted(data) || data.len() <= HEADER_LEN {
+        return Err(eyre!("Not encrypted"));
+    }
+    let (header, ciphertext) = data.split_at(HEADER_LEN);
+
+    let mut offset = MAGIC.len();
+    let log_n = header[offset];
+    offset += 1;
+    let r = u32::from_be_bytes(header[offset..offset + 4].try_into()?);
+    offset += 4;
+    let p = u32::from_be_bytes(header[offset..offset + 4].try_into()?);
+    offset += 4;
+    let salt = &header[offset..offset + SALT_LEN];
+    offset += SALT_LEN;
+    let nonce = &header[offset..offset + NONCE_LEN];
+
+    if log_n > MAX_SCRYPT_LOG_N || r > SCRYPT_R || p > SCRYPT_P {
+        return Err(eyre!("Key file asks for unsupported scrypt parameters (log_n {}, r {}, p {})", log_n, r, p));
+    }
+    let key = derive_key(passphrase, salt, log_n, r, p)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key[..]));
+    cipher
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: header })
+        .map(Zeroizing::new)
+        .map_err(|_| eyre!("Wrong passphrase or damaged file"))
+}
+
+/// Decrypts a key file, trying the passphrase that worked last before asking for one
+pub fn decrypt_file(data: &[u8], path: &Path) -> Result<PrivKey> {
+    to_privkey(unlock_file(data, path)?.0)
+}
+
+/// Decrypts a file `seal` wrote, asking for the passphrase like `decrypt_file`
+pub fn open_file(data: &[u8], path: &Path) -> Result<Zeroizing<Vec<u8>>> {
+    Ok(unlock_file(data, path)?.0)
+}
+
+/// The passphrase the key file at `path` is encrypted with, to encrypt what belongs with it
+pub fn file_passphrase(path: &Path) -> Result<Zeroizing<String>> {
+    let data = fs::read(path).wrap_err_with(|| format!("Failed to read {:?}", path))?;
+    Ok(unlock_file(&data, path)?.1)
+}
+
+/// The decrypted file and the passphrase that worked
+fn unlock_file(data: &[u8], path: &Path) -> Result<(Zeroizing<Vec<u8>>, Zeroizing<String>)> {
+    let cached = PASSPHRASE.lock().ok().and_then(|cached| cached.clone());
+    if let Some(passphrase) = cached {
+        if let Ok(plaintext) = open(data, &passphrase) {
+            return Ok((plaintext, passphrase));
+        }
+    }
+
+    let interactive = passphrase_source().is_none();
+    let attempts = if interactive { 3 } else { 1 };
+    let mut error = eyre!("No passphrase for {:?}", path);
+    for _ in 0..attempts {
+        let passphrase = Zeroizing::new(passphrase(&format!("Passphrase for {}", path.display()), false)?);
+        match open(data, &passphrase) {
+            Ok(plaintext) => {
+                if let Ok(mut cached) = PASSPHRASE.lock() {
+                    *cached = Some(passphrase.clone());
+                }
+                return Ok((plaintext, passphrase));
+            },
+            Err(e) => {
+                if interactive {
+                    eprintln!("{}", e);
+                }
+                error = e;
+            },
+        }
+    }
+    Err(error).wrap_err_with(|| format!("Failed to decrypt {:?}", path))
+}
+
+/// Where a non-interactive passphrase comes from, `None` to prompt
+fn passphrase_source() -> Option<&'static str> {
+    if env::var("KEY_PASSPHRASE").is_ok() {
+        Some("KEY_PASSPHRASE")
+    } else if CONFIG.key_passphrase_command.is_some() {
+        Some("key_passphrase_command")
+    } else {
+        None
+    }
+}
+
+/// The passphrase from `KEY_PASSPHRASE`, the output of `key_passphrase_command`,
+/// or a prompt on the terminal, asked twice when `confirm` is set
+pub fn passphrase(prompt: &str, confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = env::var("KEY_PASSPHRASE") {
+        return Ok(passphrase);
+    }
+
+    if let Some(command) = CONFIG.key_passphrase_command.as_deref() {
+        let output = shell(command).output()
+            .wrap_err_with(|| format!("Failed to run passphrase command `{}`", command))?;
+        if !output.status.success() {
+            return Err(eyre!("Passphrase command `{}` failed: {}",
+                command, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        let passphrase = String::from_utf8(output.stdout)
+            .wrap_err("Passphrase command output is not UTF-8")?;
+        return Ok(passphrase.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    if !std::io::stdin().is_terminal() {
+        return Err(eyre!("No passphrase: set KEY_PASSPHRASE or key_passphrase_command, or run in a terminal"));
+    }
+
+    let term = console::Term::stderr();
+    term.write_str(&format!("{}: ", prompt))?;
+    let passphrase = term.read_secure_line()?;
+    if passphrase.is_empty() {
+        return Err(eyre!("Empty passphrase"));
+    }
+    if confirm {
+        term.write_str("Confirm passphrase: ")?;
+        if term.read_secure_line()? != passphrase {
+            return Err(eyre!("Passphrases do not match"));
+        }
+    }
+    Ok(passphrase)
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// Writes `data` readable by the owner only
+pub fn write_private(path: &Path, data: &[u8]) -> Result<()> {
+    fs::write(path, data)
+        .wrap_err_with(|| format!("Failed to write key file: {:?}", path))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .wrap_err_with(|| format!("Failed to set permissions on {:?}", path))?;
+    }
+    Ok(())
+}
+
+/// The HOME to run `nomic` with. For an encrypted key this is a private (0700) temporary
+/// HOME holding the plaintext key, `nomic` only reads plaintext keys; the real key file is
+/// never touched. Everything else in the real HOME and its `.orga-wallet` is linked in, so
+/// the nonce and chain state stay shared. The temporary HOME is removed on drop, and left
+/// over ones from killed runs are removed by the next unlock.
+pub struct PlaintextKey {
+    home:   PathBuf,
+    /// The real `.orga-wallet`, when `home` is temporary
+    wallet: Option<PathBuf>,
+}
+
+/// Temporary HOMEs are `<runtime dir>/nomic-tools-<pid>-<random>`
+const TEMP_HOME_PREFIX: &str = "nomic-tools-";
+
+impl PlaintextKey {
+
+    /// The HOME for `home`: itself if its key is not encrypted, otherwise a temporary
+    /// HOME with the key decrypted
+    pub fn unlock(home: &Path) -> Result<Self> {
+        let wallet = home.join(".orga-wallet");
+        let path = wallet.join("privkey");
+        let encrypted = match fs::read(&path) {
+            Ok(data) if is_encrypted(&data) => data,
+            _ => return Ok(Self { home: home.to_path_buf(), wallet: None }),
+        };
+        let privkey = decrypt_file(&encrypted, &path)?;
+
+        remove_stale_homes();
+        let temp_home = runtime_dir().join(format!(
+            "{}{}-{:016x}", TEMP_HOME_PREFIX, std::process::id(), rand::thread_rng().next_u64(),
+        ));
+        create_private_dir(&temp_home)?;
+        // From here the drop cleans up
+        let plaintext = Self { home: temp_home, wallet: Some(wallet.clone()) };
+
+        let temp_wallet = plaintext.home.join(".orga-wallet");
+        create_private_dir(&temp_wallet)?;
+        link_entries(home, &plaintext.home, &[".orga-wallet"])?;
+        link_entries(&wallet, &temp_wallet, &["privkey"])?;
+        write_private(&temp_wallet.join("privkey"), privkey.bytes())?;
+        Ok(plaintext)
+    }
+
+    /// Sets the HOME of `cmd`, a nomic command, to what `unlock` gives for `home`.
+    /// The key stays decrypted until the returned value is dropped
+    pub fn set_home(cmd: &mut Command, home: &Path) -> Result<Self> {
+        let plaintext = Self::unlock(home)?;
+        cmd.env("HOME", plaintext.home());
+        Ok(plaintext)
+    }
+
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+}
+
+impl Drop for PlaintextKey {
+    fn drop(&mut self) {
+        let Some(wallet) = &self.wallet else {
+            return;
+        };
+        let temp_wallet = self.home.join(".orga-wallet");
+
+        // Files nomic created or replaced instead of writing through the links, e.g. a first nonce
+        if let Ok(entries) = fs::read_dir(&temp_wallet) {
+            for entry in entries.flatten() {
+                let is_file = entry.file_type().map(|file_type| file_type.is_file()).unwrap_or(false);
+                if is_file && entry.file_name() != "privkey" {
+                    if let Err(e) = fs::copy(entry.path(), wallet.join(entry.file_name())) {
+                        warn!("Failed to copy {:?} back to {:?}: {}", entry.path(), wallet, e);
+                    }
+                }
+            }
+        }
+
+        if let Err(e) = remove_home(&self.home) {
+            warn!("Failed to remove temporary home {:?}: {}", self.home, e);
+            eprintln!("Warning: plaintext key left in {:?}, remove it", self.home);
+        }
+    }
+}
+
+/// Where temporary HOMEs go: the per-user runtime directory (usually memory backed) if there is one
+fn runtime_dir() -> PathBuf {
+    env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(env::temp_dir)
+}
+
+fn create_private_dir(path: &Path) -> Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(path)
+        .wrap_err_with(|| format!("Failed to create private directory {:?}", path))
+}
+
+/// Links every entry of `from` into `to`, except `skip`
+fn link_entries(from: &Path, to: &Path, skip: &[&str]) -> Result<()> {
+    let entries = match fs::read_dir(from) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(()),
+    };
+    for entry in entries.flatten() {
+        if skip.iter().any(|name| entry.file_name() == *name) {
+            continue;
+        }
+        link(&entry.path(), &to.join(entry.file_name()))
+            .wrap_err_with(|| format!("Failed to link {:?} into {:?}", entry.path(), to))?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn link(original: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(not(unix))]
+fn link(original: &Path, link: &Path) -> std::io::Result<()> {
+    if original.is_dir() {
+        std::os::windows::fs::symlink_dir(original, link)
+    } else {
+        std::os::windows::fs::symlink_file(original, link)
+    }
+}
+
+/// Overwrites the plaintext key and removes a temporary HOME, links are removed, not followed
+fn remove_home(home: &Path) -> std::io::Result<()> {
+    let privkey = home.join(".orga-wallet").join("privkey");
+    if fs::symlink_metadata(&privkey).map(|metadata| metadata.is_file()).unwrap_or(false) {
+        fs::write(&privkey, [0u8; 32])?;
+    }
+    fs::remove_dir_all(home)
+}
+
+/// Removes temporary HOMEs left by runs that were killed before they could clean up
+fn remove_stale_homes() {
+    let Ok(entries) = fs::read_dir(runtime_dir()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(pid) = name.strip_prefix(TEMP_HOME_PREFIX)
+            .and_then(|rest| rest.split_once('-'))
+            .and_then(|(pid, _)| pid.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if pid != std::process::id() && is_stale(&entry.path(), pid) {
+            if let Err(e) = remove_home(&entry.path()) {
+                warn!("Failed to remove stale temporary home {:?}: {}", entry.path(), e);
+            }
+        }
+    }
+}
+
+/// `true` for our own temporary HOME whose process is gone
+#[cfg(target_os = "linux")]
+fn is_stale(home: &Path, pid: u32) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let owner = |path: &Path| fs::symlink_metadata(path).map(|metadata| metadata.uid()).ok();
+    owner(home).is_some()
+        && owner(home) == owner(Path::new("/proc/self"))
+        && !Path::new("/proc").join(pid.to_string()).exists()
+}
+
+/// Without a cheap check for the process, leave it
+#[cfg(not(target_os = "linux"))]
+fn is_stale(_home: &Path, _pid: u32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG_N: usize = MAGIC.len();
+    const R: usize = LOG_N + 1;
+    const SALT: usize = R + 8;
+    const NONCE: usize = SALT + SALT_LEN;
+
+    fn privkey() -> PrivKey {
+        PrivKey::new(Some([0x42; 32]))
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = encrypt(&privkey(), "correct horse").unwrap();
+        assert!(is_encrypted(&data));
+        assert_eq!(decrypt(&data, "correct horse").unwrap().bytes(), privkey().bytes());
+        assert!(decrypt(&data, "wrong horse").is_err());
+    }
+
+    #[test]
+    fn header_is_authenticated() {
+        let data = encrypt(&privkey(), "correct horse").unwrap();
+        // A cheaper scrypt, a different salt and a different nonce all fail
+        for index in [LOG_N, SALT, NONCE + NONCE_LEN - 1] {
+            let mut tampered = data.clone();
+            tampered[index] ^= 1;
+            assert!(decrypt(&tampered, "correct horse").is_err(), "byte {}", index);
+        }
+        let mut truncated = data.clone();
+        truncated.truncate(HEADER_LEN);
+        assert!(decrypt(&truncated, "correct horse").is_err());
+    }
+
+    #[test]
+    fn costly_parameters_are_rejected() {
+        let data = encrypt(&privkey(), "correct horse").unwrap();
+
+        let mut tampered = data.clone();
+        tampered[LOG_N] = MAX_SCRYPT_LOG_N + 1;
+        let error = decrypt(&tampered, "correct horse").err().unwrap();
+        assert!(error.to_string().contains("unsupported scrypt parameters"));
+
+        let mut tampered = data.clone();
+        tampered[R..R + 4].copy_from_slice(&(SCRYPT_R + 1).to_be_bytes());
+        let error = decrypt(&tampered, "correct horse").err().unwrap();
+        assert!(error.to_string().contains("unsupported scrypt parameters"));
+    }
+
+    #[test]
+    fn seal_and_open_any_secret() {
+        let data = seal(b"abandon art\n", "correct horse").unwrap();
+        assert_eq!(open(&data, "correct horse").unwrap().as_slice(), b"abandon art\n");
+        // Not a key
+        assert!(decrypt(&data, "correct horse").is_err());
+    }
+
+    #[test]
+    fn unlock_uses_a_private_temporary_home() {
+        let home = tempfile::tempdir().unwrap();
+        let wallet = home.path().join(".orga-wallet");
+        fs::create_dir(&wallet).unwrap();
+        let encrypted = encrypt(&privkey(), "correct horse").unwrap();
+        write_private(&wallet.join("privkey"), &encrypted).unwrap();
+        fs::write(wallet.join("nonce"), "7").unwrap();
+        *PASSPHRASE.lock().unwrap() = Some(Zeroizing::new("correct horse".to_string()));
+
+        let plaintext = PlaintextKey::unlock(home.path()).unwrap();
+        let temp_home = plaintext.home().to_path_buf();
+        assert_ne!(temp_home, home.path());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&temp_home).unwrap().permissions().mode() & 0o777, 0o700);
+        }
+        let temp_wallet = temp_home.join(".orga-wallet");
+        assert_eq!(fs::read(temp_wallet.join("privkey")).unwrap(), privkey().bytes());
+        assert_eq!(fs::read_to_string(temp_wallet.join("nonce")).unwrap(), "7");
+        fs::write(temp_wallet.join("created"), "by nomic").unwrap();
+        drop(plaintext);
+
+        assert!(!temp_home.exists());
+        assert_eq!(fs::read(wallet.join("privkey")).unwrap(), encrypted);
+        assert_eq!(fs::read_to_string(wallet.join("created")).unwrap(), "by nomic");
+        assert_eq!(fs::read_to_string(wallet.join("nonce")).unwrap(), "7");
+    }
+
+    #[test]
+    fn unlock_leaves_a_plaintext_key_home() {
+        let home = tempfile::tempdir().unwrap();
+        let wallet = home.path().join(".orga-wallet");
+        fs::create_dir(&wallet).unwrap();
+        write_private(&wallet.join("privkey"), privkey().bytes()).unwrap();
+
+        let plaintext = PlaintextKey::unlock(home.path()).unwrap();
+        assert_eq!(plaintext.home(), home.path());
+        drop(plaintext);
+        assert_eq!(fs::read(wallet.join("privkey")).unwrap(), privkey().bytes());
+    }
+}
@@ -0,0 +1,50 @@
+use clap::Parser;
+use crate::privkey::audit;
+use eyre::eyre;
+use eyre::Result;
+use std::io::{self, IsTerminal, Write};
+
+/// Flags for commands that print key material
+#[derive(Parser, Debug)]
+pub struct ExportArgs {
+    /// Skip the confirmation prompt
+    #[arg(long)]
+    pub i_understand: bool,
+
+    /// Print even when the output is not a terminal, e.g. to a pipe or file
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl ExportArgs {
+
+    /// Checks it is safe to print the `what` of `subject`, asking for confirmation unless
+    /// `--i-understand`, and records the outcome in the audit log
+    pub fn confirm(&self, what: &str, subject: &str) -> Result<()> {
+        let event = format!("export-{}", what.replace(' ', "-"));
+
+        if !io::stdout().is_terminal() && !self.force {
+            audit::record(&event, subject, "refused: output is not a terminal");
+            return Err(eyre!("Refusing to write the {} to a non-terminal, use --force if this is intended", what));
+        }
+
+        if !self.i_understand {
+            if !io::stdin().is_terminal() {
+                audit::record(&event, subject, "refused: not confirmed");
+                return Err(eyre!("Exporting the {} needs confirmation, use --i-understand", what));
+            }
+            eprint!("The {} of {} controls its funds, anyone who sees it can take them. Print it? [y/N]: ", what, subject);
+            io::stderr().flush()?;
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                audit::record(&event, subject, "cancelled");
+                return Err(eyre!("Export cancelled"));
+            }
+        }
+
+        let detail = if io::stdout().is_terminal() { "printed to terminal" } else { "printed to non-terminal (forced)" };
+        audit::record(&event, subject, detail);
+        Ok(())
+    }
+}
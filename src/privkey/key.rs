@@ -6,6 +6,9 @@ use eyre::eyre;
 use eyre::Result;
 use eyre::WrapErr;
 use crate::functions::read_stdin;
+use crate::privkey::encrypted;
+use crate::privkey::mnemonic;
+use crate::privkey::HdPath;
 use log::info;
 use once_cell::sync::OnceCell;
 use rand::Rng;
@@ -14,6 +17,7 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use zeroize::{Zeroize, Zeroizing};
 
 
 ///// Retrieves the private key based on the provided input, relative path, or bytes.
@@ -153,14 +157,13 @@ use std::path::PathBuf;
 
 /// Represents a private key.
 ///
-/// This struct encapsulates a 32-byte private key along with its hex representation, signing key, 
-/// public key, account ID, and address. The fields are lazily initialized to improve performance 
-/// and resource usage.
+/// This struct encapsulates a 32-byte private key along with its signing key, public key,
+/// account ID, and address. The fields are lazily initialized to improve performance 
+/// and resource usage. The key bytes are zeroized on drop, the hex form is never cached.
 ///
 /// # Fields
 ///
 /// * `bytes`: 32-byte representation of the private key.
-/// * `hex`: Hexadecimal representation of the private key as a string.
 /// * `signing_key`: cosmrs Signing key derived from the private key.
 /// * `secret_key` : orga Signing key derived from the private key.
 /// * `public_key` : Public key derived from the signing key.
@@ -169,8 +172,6 @@ use std::path::PathBuf;
 pub struct PrivKey {
     /// 32-byte representation of the private key.
     bytes: [u8; 32],
-    /// Hexadecimal representation of the private key as a string.
-    hex:         OnceCell<String>,
     /// cosmrs Signing key derived from the private key.
     signing_key: OnceCell<SigningKey>,
     /// cosmrs Publickey derived from the signing key.
@@ -207,7 +208,6 @@ impl PrivKey {
 
         Self {
             bytes,
-            hex: OnceCell::new(),
             signing_key: OnceCell::new(),
             public_key: OnceCell::new(),
             account_id: OnceCell::new(),
@@ -279,9 +279,12 @@ impl PrivKey {
             match fs::read(path) {
                 Ok(file_data) => {
                     // Ensure file data is not empty
+                    if encrypted::is_encrypted(&file_data) {
+                        return encrypted::decrypt_file(&file_data, path);
+                    }
                     if !file_data.is_empty() {
                         // Recursively apply checks to the file content
-                        return Self::from_vec(file_data);
+                        return Self::from_vec(Zeroizing::new(file_data));
                     } else {
                         info!("File is empty: {:?}", path);
                         return Ok(Self::new(None));
@@ -306,11 +309,11 @@ impl PrivKey {
         }
 
         // Otherwise, attempt to interpret the bytes as a UTF-8 string.
-        if let Ok(text_data) = String::from_utf8(buf.to_vec()) {
+        if let Ok(text_data) = String::from_utf8(buf.to_vec()).map(Zeroizing::new) {
             // Check if it's a valid 64-character hex string.
             if text_data.len() == 64 && text_data.chars().all(|c| c.is_digit(16)) {
                 // Decode the hex string into a vector of bytes
-                if let Ok(decoded_bytes) = hex::decode(&text_data) {
+                if let Ok(decoded_bytes) = hex::decode(text_data.as_str()).map(Zeroizing::new) {
                     // Convert the decoded vector to [u8; 32] if it's exactly 32 bytes
                     if decoded_bytes.len() == 32 {
                         let mut binary_data = [0u8; 32];
@@ -397,6 +400,11 @@ impl PrivKey {
         let path: &Path = path.as_ref();
         let path_str = path.to_string_lossy();
 
+        // Never replace an encrypted key because it could not be decrypted
+        if encrypted::is_encrypted_file(path) {
+            return Self::import(path_str);
+        }
+
         // Attempt to import the key from the file
         if let Ok(key_instance) = Self::import(path_str) {
             return Ok(key_instance);
@@ -416,6 +424,16 @@ impl PrivKey {
         Err(eyre!("Key file does not exist and 'new' is set to false"))
     }
 
+    /// Derives a private key from a 24 word BIP39 mnemonic, as Keplr does.
+    ///
+    /// # Parameters
+    /// - `phrase`: The mnemonic words, separated by whitespace.
+    /// - `password`: The optional BIP39 passphrase, empty for none.
+    /// - `hd_path`: The derivation path with its account and address index.
+    pub fn from_mnemonic(phrase: &str, password: &str, hd_path: &HdPath) -> Result<Self> {
+        mnemonic::derive(phrase, password, hd_path)
+    }
+
     /// Reads a private key from standard input (stdin).
     /// 
     /// This function will read data from stdin for a specified number of attempts and timeout duration.
@@ -474,14 +492,14 @@ impl PrivKey {
 
     /// Retrieves the hex-encoded private key.
     ///
-    /// This method ensures that the hex representation is computed lazily and only when needed.
+    /// The hex string is built on every call and zeroized when the returned value is dropped,
+    /// keep it no longer than needed.
     ///
     /// # Returns
     ///
-    /// Returns a `Result<&str, eyre::Report>` containing the hex-encoded private key or an
-    /// error if the computation fails.
-    pub fn export(&self) -> &str {
-        self.hex.get_or_init(|| hex::encode(self.bytes)).as_str()
+    /// Returns a `Zeroizing<String>` containing the hex-encoded private key.
+    pub fn export(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(self.bytes))
     }
 
     /// Lazily retrieves or computes the signing key from the private key bytes.
@@ -535,6 +553,28 @@ impl PrivKey {
         }).map(|s| s.as_str())
     }
 
+    /// The address of this key on another chain, e.g. `cosmos` or `osmo`
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<String, eyre::Report>` containing the bech32 address with the
+    /// given human readable prefix, or an error if the prefix is not valid.
+    pub fn address_with_prefix(&self, prefix: &str) -> Result<String> {
+        self.public_key()?.account_id(prefix)
+            .map(|account_id| account_id.to_string())
+            .map_err(|e| eyre::eyre!("Failed to get {} address from public key: {}", prefix, e))
+    }
+
+    /// Addresses for each of `prefixes`, in the same order
+    pub fn addresses_with_prefixes<S: AsRef<str>>(&self, prefixes: &[S]) -> Result<Vec<(String, String)>> {
+        prefixes.iter()
+            .map(|prefix| {
+                let prefix = prefix.as_ref();
+                Ok((prefix.to_string(), self.address_with_prefix(prefix)?))
+            })
+            .collect()
+    }
+
     /// Saves the key to a binary file at the given path, with a `force` flag to control overwriting.
     pub fn save<P: AsRef<Path>>(&self,
         file_path: P,
@@ -566,6 +606,21 @@ impl PrivKey {
         }
     }
 
+    /// Saves the key encrypted with `passphrase`, with a `force` flag to control overwriting.
+    pub fn save_encrypted<P: AsRef<Path>>(&self,
+        file_path: P,
+        passphrase: &str,
+        force: bool,
+    ) -> Result<()> {
+        let path = file_path.as_ref();
+        if path.exists() && !force {
+            return Err(eyre::eyre!("File already exists at {:?} and `force` is not enabled.", path));
+        }
+        encrypted::write_private(path, &encrypted::encrypt(self, passphrase)?)?;
+        println!("Encrypted PrivKey successfully saved to {:?}", path);
+        Ok(())
+    }
+
 //    pub fn save_to_file_or_home<P: AsRef<Path>>(&self,
 //        file: Option<P>,
 //        home: Option<P>,
@@ -591,6 +646,12 @@ impl std::fmt::Debug for PrivKey {
     }
 }
 
+impl Drop for PrivKey {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
 impl PartialEq for PrivKey {
     fn eq(&self, other: &Self) -> bool {
         self.bytes.eq_ignore_ascii_case(&other.bytes)
@@ -608,7 +669,7 @@ impl PartialEq for PrivKey {
 /// while adhering to the constraints of the underlying cryptographic types.
 impl Clone for PrivKey {
     fn clone(&self) -> Self {
-        Self::new(Some(self.bytes.clone()))
+        Self::new(Some(self.bytes))
     }
 }
 
@@ -738,3 +799,32 @@ impl FromPath for PathBuf {
     }
 }
 
+
+/// Key bytes 0x01 to 0x20, the fixed key of the test vectors
+#[cfg(test)]
+pub(crate) fn test_key() -> PrivKey {
+    PrivKey::new(Some(std::array::from_fn(|i| i as u8 + 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_with_prefix() {
+        let key = test_key();
+        assert_eq!(key.address().unwrap(), "nomic1tp7fhly84qm6q4hhzmp0nh5frtdugmysqjs0rr");
+        assert_eq!(key.address_with_prefix("nomic").unwrap(), key.address().unwrap());
+        assert_eq!(key.address_with_prefix("cosmos").unwrap(), "cosmos1tp7fhly84qm6q4hhzmp0nh5frtdugmysu2r4sf");
+        assert!(key.address_with_prefix("Osmo").is_err());
+    }
+
+    #[test]
+    fn addresses_with_prefixes_keep_order() {
+        let addresses = test_key().addresses_with_prefixes(&["cosmos", "nomic"]).unwrap();
+        assert_eq!(addresses, [
+            ("cosmos".to_string(), "cosmos1tp7fhly84qm6q4hhzmp0nh5frtdugmysu2r4sf".to_string()),
+            ("nomic".to_string(), "nomic1tp7fhly84qm6q4hhzmp0nh5frtdugmysqjs0rr".to_string()),
+        ]);
+    }
+}
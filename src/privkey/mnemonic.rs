@@ -0,0 +1,227 @@
+use bip32::{DerivationPath, Language, Mnemonic, XPrv};
+use crate::privkey::encrypted;
+use crate::privkey::PrivKey;
+use eyre::eyre;
+use eyre::Result;
+use eyre::WrapErr;
+use rand::rngs::OsRng;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// Cosmos coin type 118, the path Keplr uses
+pub const DEFAULT_HD_PATH: &str = "m/44'/118'/{account}'/0/{index}";
+
+/// Where a derived key came from, `{account}` and `{index}` in `path` are filled in
+#[derive(Clone, Debug)]
+pub struct HdPath {
+    pub path:    String,
+    pub account: u32,
+    pub index:   u32,
+}
+
+impl Default for HdPath {
+    fn default() -> Self {
+        Self { path: DEFAULT_HD_PATH.to_string(), account: 0, index: 0 }
+    }
+}
+
+impl HdPath {
+
+    pub fn new(path: Option<&str>, account: u32, index: u32) -> Self {
+        Self {
+            path: path.unwrap_or(DEFAULT_HD_PATH).to_string(),
+            account,
+            index,
+        }
+    }
+
+    pub fn derivation_path(&self) -> Result<DerivationPath> {
+        let path = self.path
+            .replace("{account}", &self.account.to_string())
+            .replace("{index}", &self.index.to_string());
+        path.parse()
+            .map_err(|e| eyre!("Invalid derivation path {}: {}", path, e))
+    }
+}
+
+impl std::fmt::Display for HdPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.derivation_path() {
+            Ok(path) => write!(f, "{}", path),
+            Err(_) => write!(f, "{}", self.path),
+        }
+    }
+}
+
+/// A new random 24 word English mnemonic
+pub fn generate() -> String {
+    Mnemonic::random(OsRng, Language::English).phrase().to_string()
+}
+
+/// Words separated by single spaces, lowercase
+pub fn normalize(phrase: &str) -> String {
+    phrase.split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Checks the words and the checksum, only 24 word mnemonics are supported
+pub fn validate(phrase: &str) -> Result<Mnemonic> {
+    let phrase = normalize(phrase);
+    let words = phrase.split(' ').count();
+    if words != 24 {
+        return Err(eyre!("Expected a 24 word mnemonic, got {} words", words));
+    }
+    Mnemonic::new(&phrase, Language::English)
+        .map_err(|_| eyre!("Invalid mnemonic: unknown word or bad checksum"))
+}
+
+/// Derives the key at `hd_path`, `password` is the optional BIP39 passphrase
+pub fn derive(phrase: &str, password: &str, hd_path: &HdPath) -> Result<PrivKey> {
+    let mnemonic = validate(phrase)?;
+    let seed = mnemonic.to_seed(password);
+    let xprv = XPrv::derive_from_path(&seed, &hd_path.derivation_path()?)
+        .map_err(|e| eyre!("Failed to derive key at {}: {}", hd_path, e))?;
+    Ok(PrivKey::new(Some(xprv.to_bytes())))
+}
+
+/// Reads a mnemonic from a file, the argument itself, stdin, or a hidden prompt
+pub fn read(input: Option<&str>) -> Result<String> {
+    match input {
+        Some(input) if Path::new(input).is_file() => {
+            fs::read_to_string(input)
+                .map(|phrase| normalize(&phrase))
+                .wrap_err_with(|| format!("Failed to read mnemonic from {}", input))
+        },
+        Some(phrase) => {
+            eprintln!("Warning: A mnemonic given as an argument is kept in shell history and shown by `ps`, pipe it or leave it out to be prompted");
+            Ok(normalize(phrase))
+        },
+        None => {
+            if !std::io::stdin().is_terminal() {
+                let mut phrase = String::new();
+                std::io::stdin().read_line(&mut phrase)?;
+                return Ok(normalize(&phrase));
+            }
+            let term = console::Term::stderr();
+            term.write_str("Mnemonic: ")?;
+            Ok(normalize(&term.read_secure_line()?))
+        },
+    }
+}
+
+/// The mnemonic kept next to a key file by `import --keep-mnemonic`
+pub fn file(key_file: &Path) -> PathBuf {
+    key_file.with_file_name("mnemonic")
+}
+
+/// Keeps the mnemonic next to the key file, readable by the owner only and encrypted
+/// with the key's passphrase when the key is encrypted. `true` if it was encrypted
+pub fn save(key_file: &Path, phrase: &str) -> Result<bool> {
+    let passphrase = match encrypted::is_encrypted_file(key_file) {
+        true => Some(encrypted::file_passphrase(key_file)?),
+        false => None,
+    };
+    write(key_file, phrase, passphrase.as_ref().map(|passphrase| passphrase.as_str()))?;
+    Ok(passphrase.is_some())
+}
+
+fn write(key_file: &Path, phrase: &str, passphrase: Option<&str>) -> Result<()> {
+    let content = Zeroizing::new(format!("{}\n", normalize(phrase)));
+    match passphrase {
+        Some(passphrase) => encrypted::write_private(&file(key_file), &encrypted::seal(content.as_bytes(), passphrase)?),
+        None => encrypted::write_private(&file(key_file), content.as_bytes()),
+    }
+}
+
+/// The kept mnemonic as it is stored, decrypted if needed, `None` if there is none
+fn read_kept(key_file: &Path) -> Result<Option<Zeroizing<String>>> {
+    let path = file(key_file);
+    let data = match fs::read(&path) {
+        Ok(data) => Zeroizing::new(data),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read mnemonic from {:?}", path)),
+    };
+    let data = match encrypted::is_encrypted(&data) {
+        true => encrypted::open_file(&data, &path)?,
+        false => data,
+    };
+    let phrase = std::str::from_utf8(&data)
+        .map_err(|_| eyre!("Mnemonic at {:?} is not text", path))?;
+    Ok(Some(Zeroizing::new(normalize(phrase))))
+}
+
+/// Rewrites a kept mnemonic, if any, encrypted with `passphrase` or in plaintext,
+/// so it follows its key through `key encrypt` and `key decrypt`
+pub fn reseal(key_file: &Path, passphrase: Option<&str>) -> Result<()> {
+    if let Some(phrase) = read_kept(key_file)? {
+        write(key_file, &phrase, passphrase)?;
+    }
+    Ok(())
+}
+
+/// The kept mnemonic, checked against the key it is stored with
+pub fn load(key_file: &Path, password: &str, hd_path: &HdPath) -> Result<String> {
+    let path = file(key_file);
+    let phrase = read_kept(key_file)?
+        .ok_or_else(|| eyre!("No mnemonic kept at {:?}, a key cannot be turned back into one", path))?;
+
+    let key = PrivKey::load(key_file, false)?;
+    if derive(&phrase, password, hd_path)?.bytes() != key.bytes() {
+        return Err(eyre!("Mnemonic at {:?} does not derive the key in {:?} at {}", path, key_file, hd_path));
+    }
+    Ok(phrase.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The BIP39 test mnemonic, "abandon" 23 times and "art"
+    fn abandon_art() -> String {
+        format!("{}art", "abandon ".repeat(23))
+    }
+
+    #[test]
+    fn derives_the_keplr_key() {
+        let privkey = derive(&abandon_art(), "", &HdPath::default()).unwrap();
+        assert_eq!(hex::encode(privkey.bytes()), "8088c2ed2149c34f6d6533b774da4e1692eb5cb426fdbaef6898eeda489630b7");
+        assert_eq!(privkey.address().unwrap(), "nomic1r5v5srda7xfth3hn2s26txvrcrntldju8n5py4");
+        assert_eq!(privkey.address_with_prefix("cosmos").unwrap(), "cosmos1r5v5srda7xfth3hn2s26txvrcrntldjumt8mhl");
+    }
+
+    #[test]
+    fn path_and_passphrase_change_the_key() {
+        let key = derive(&abandon_art(), "", &HdPath::default()).unwrap();
+        let index = derive(&abandon_art(), "", &HdPath::new(None, 0, 1)).unwrap();
+        let account = derive(&abandon_art(), "", &HdPath::new(None, 1, 0)).unwrap();
+        let password = derive(&abandon_art(), "TREZOR", &HdPath::default()).unwrap();
+        for other in [index, account, password] {
+            assert_ne!(other.bytes(), key.bytes());
+        }
+    }
+
+    #[test]
+    fn normalizes_before_deriving() {
+        let messy = format!("  {}\n", abandon_art().to_uppercase().replace(' ', "\t "));
+        assert_eq!(normalize(&messy), abandon_art());
+        assert!(validate(&messy).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_mnemonics() {
+        let phrase = abandon_art();
+        // 23 words
+        let short = phrase.rsplit_once(' ').unwrap().0;
+        assert!(validate(short).err().unwrap().to_string().contains("23 words"));
+        // Right length, wrong checksum word
+        let bad_checksum = format!("{}abandon", "abandon ".repeat(23));
+        assert!(validate(&bad_checksum).err().unwrap().to_string().contains("bad checksum"));
+        // Not a BIP39 word
+        let unknown = format!("{}artt", "abandon ".repeat(23));
+        assert!(validate(&unknown).is_err());
+    }
+}
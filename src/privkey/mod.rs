@@ -1,7 +1,20 @@
+mod audit;
 mod cli;
+mod encrypted;
+mod guard;
 mod key;
+mod message;
+pub mod mnemonic;
+mod shamir;
+mod sign;
 
 pub use cli::Cli;
+pub use cli::HdPathArgs;
+pub use encrypted::PlaintextKey;
+pub use guard::ExportArgs;
 pub use key::FromPath;
 pub use key::PrivKey;
-
+pub use message::SignedMessage;
+pub use mnemonic::HdPath;
+pub use shamir::Share;
+pub use sign::{SignedTx, TxMessage, TxParams};
@@ -0,0 +1,293 @@
+use crate::privkey::encrypted;
+use crate::privkey::PrivKey;
+use eyre::eyre;
+use eyre::Result;
+use eyre::WrapErr;
+use flate2::Crc;
+use itertools::Itertools;
+use rand::RngCore;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use zeroize::{Zeroize, Zeroizing};
+
+const PREFIX: &str = "nomic-share-v1";
+
+/// Multiplication in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// a^254 is the inverse of a, for a != 0
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent != 0 {
+        if exponent & 1 != 0 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Evaluates the polynomial with `coefficients` (constant first) at `x`
+fn gf_eval(coefficients: &[u8], x: u8) -> u8 {
+    coefficients.iter().rev().fold(0, |acc, &coefficient| gf_mul(acc, x) ^ coefficient)
+}
+
+/// One share of a key, any `threshold` distinct shares rebuild it.
+///
+/// Encoded as `nomic-share-v1:<address>:<threshold>:<index>:<hex>:<crc32>`,
+/// the CRC-32 covers everything before it.
+pub struct Share {
+    pub address:   String,
+    pub threshold: u8,
+    /// The x coordinate, 1 to 255
+    pub index:     u8,
+    data:          [u8; 32],
+}
+
+impl Share {
+
+    /// Splits the key into `shares` shares, `threshold` of which are needed to rebuild it
+    pub fn split(privkey: &PrivKey, shares: u8, threshold: u8) -> Result<Vec<Self>> {
+        if threshold < 2 {
+            return Err(eyre!("Threshold must be at least 2"));
+        }
+        if shares < threshold {
+            return Err(eyre!("Shares ({}) must be at least the threshold ({})", shares, threshold));
+        }
+        let address = privkey.address()?.to_string();
+
+        // One random polynomial per key byte, the key byte is its constant term
+        let mut polynomials = Zeroizing::new(vec![0u8; 32 * threshold as usize]);
+        rand::thread_rng().fill_bytes(&mut polynomials);
+        for (byte, polynomial) in privkey.bytes().iter().zip(polynomials.chunks_mut(threshold as usize)) {
+            polynomial[0] = *byte;
+        }
+
+        Ok((1..=shares)
+            .map(|index| {
+                let mut data = [0u8; 32];
+                for (y, polynomial) in data.iter_mut().zip(polynomials.chunks(threshold as usize)) {
+                    *y = gf_eval(polynomial, index);
+                }
+                Self { address: address.clone(), threshold, index, data }
+            })
+            .collect())
+    }
+
+    /// Rebuilds the key from at least `threshold` shares of the same key,
+    /// and checks it has the address the shares were made for. With more than
+    /// `threshold` shares, a wrong share is worked around by trying other sets of them
+    pub fn combine(shares: &[Self]) -> Result<PrivKey> {
+        let first = shares.first().ok_or_else(|| eyre!("No shares"))?;
+        if let Some(other) = shares.iter().find(|share| share.address != first.address || share.threshold != first.threshold) {
+            return Err(eyre!("Share {} is for {} (threshold {}), not {} (threshold {})",
+                other.index, other.address, other.threshold, first.address, first.threshold));
+        }
+
+        let threshold = first.threshold as usize;
+        let mut seen = HashSet::new();
+        let shares: Vec<&Self> = shares.iter().filter(|share| seen.insert(share.index)).collect();
+        if shares.len() < threshold {
+            return Err(eyre!("{} distinct shares given, {} needed", shares.len(), threshold));
+        }
+
+        for subset in shares.iter().copied().combinations(threshold) {
+            let privkey = PrivKey::new(Some(*Self::interpolate(&subset)));
+            if privkey.address()? == first.address {
+                return Ok(privkey);
+            }
+        }
+        if shares.len() == threshold {
+            return Err(eyre!("Rebuilt key is not for {}: a share is wrong", first.address));
+        }
+        // Any `threshold` right shares would have rebuilt it
+        Err(eyre!("No {} of the {} shares rebuild the key for {}: at least {} of them are wrong",
+            threshold, shares.len(), first.address, shares.len() - threshold + 1))
+    }
+
+    /// Lagrange interpolation at x = 0, subtraction is xor in GF(2^8)
+    fn interpolate(shares: &[&Self]) -> Zeroizing<[u8; 32]> {
+        let mut secret = Zeroizing::new([0u8; 32]);
+        for share in shares {
+            let mut basis = 1u8;
+            for other in shares.iter().filter(|other| other.index != share.index) {
+                basis = gf_mul(basis, gf_mul(other.index, gf_inv(other.index ^ share.index)));
+            }
+            for (byte, y) in secret.iter_mut().zip(share.data.iter()) {
+                *byte ^= gf_mul(*y, basis);
+            }
+        }
+        secret
+    }
+
+    fn checksum(body: &str) -> u32 {
+        let mut crc = Crc::new();
+        crc.update(body.as_bytes());
+        crc.sum()
+    }
+
+    fn body(&self) -> Zeroizing<String> {
+        Zeroizing::new(format!("{}:{}:{}:{}:{}",
+            PREFIX, self.address, self.threshold, self.index, hex::encode(self.data)))
+    }
+
+    /// The printable share
+    pub fn encode(&self) -> Zeroizing<String> {
+        let body = self.body();
+        Zeroizing::new(format!("{}:{:08x}", body.as_str(), Self::checksum(&body)))
+    }
+
+    /// A share, or the file holding one
+    pub fn read(input: &str) -> Result<Self> {
+        let path = Path::new(input);
+        if path.is_file() {
+            let content = Zeroizing::new(fs::read_to_string(path)
+                .wrap_err_with(|| format!("Failed to read share file {:?}", path))?);
+            return content.parse().wrap_err_with(|| format!("Invalid share file {:?}", path));
+        }
+        input.parse()
+    }
+
+    /// Writes the share to `DIR/<address>-share-<index>.txt`, readable by the owner only
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(format!("{}-share-{}.txt", self.address, self.index));
+        if path.exists() {
+            return Err(eyre!("Share file {:?} already exists", path));
+        }
+        let mut content = self.encode();
+        content.push('\n');
+        encrypted::write_private(&path, content.as_bytes())?;
+        Ok(path)
+    }
+}
+
+impl FromStr for Share {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let (body, checksum) = value.rsplit_once(':')
+            .ok_or_else(|| eyre!("Not a key share"))?;
+        if u32::from_str_radix(checksum, 16).ok() != Some(Self::checksum(body)) {
+            return Err(eyre!("Share checksum does not match, it was mistyped or damaged"));
+        }
+
+        let parts: Vec<&str> = body.split(':').collect();
+        let [prefix, address, threshold, index, data] = parts.as_slice() else {
+            return Err(eyre!("Not a key share"));
+        };
+        if *prefix != PREFIX {
+            return Err(eyre!("Unsupported share format {}", prefix));
+        }
+
+        let bytes = Zeroizing::new(hex::decode(data).map_err(|_| eyre!("Share data is not hex"))?);
+        let data: [u8; 32] = bytes.as_slice().try_into()
+            .map_err(|_| eyre!("Share data is not 32 bytes"))?;
+
+        let index: u8 = index.parse().map_err(|_| eyre!("Invalid share index {}", index))?;
+        if index == 0 {
+            return Err(eyre!("Invalid share index 0"));
+        }
+        Ok(Self {
+            address:   address.to_string(),
+            threshold: threshold.parse().map_err(|_| eyre!("Invalid share threshold {}", threshold))?,
+            index,
+            data,
+        })
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::privkey::key::test_key;
+
+    #[test]
+    fn gf_identities() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, 1), a);
+            assert_eq!(gf_mul(a, 0), 0);
+            assert_eq!(gf_mul(a, gf_inv(a)), 1, "inverse of {}", a);
+            for b in 1..=255u8 {
+                assert_eq!(gf_mul(a, b), gf_mul(b, a));
+            }
+        }
+        // The AES field, {53} * {CA} = 1
+        assert_eq!(gf_mul(0x53, 0xca), 1);
+    }
+
+    #[test]
+    fn any_threshold_shares_combine() {
+        let privkey = test_key();
+        let shares = Share::split(&privkey, 5, 3).unwrap();
+        assert_eq!(shares.len(), 5);
+        for subset in (0..5).combinations(3) {
+            let subset: Vec<Share> = subset.iter().map(|&i| shares[i].encode().parse().unwrap()).collect();
+            assert_eq!(Share::combine(&subset).unwrap().bytes(), privkey.bytes());
+        }
+    }
+
+    #[test]
+    fn fewer_than_threshold_fail() {
+        let shares = Share::split(&test_key(), 5, 3).unwrap();
+        let mut two: Vec<Share> = shares[..2].iter().map(|share| share.encode().parse().unwrap()).collect();
+        assert!(Share::combine(&two).is_err());
+        // A repeated share does not count twice
+        two.push(shares[0].encode().parse().unwrap());
+        assert!(Share::combine(&two).is_err());
+    }
+
+    #[test]
+    fn wrong_share_is_worked_around() {
+        let privkey = test_key();
+        let mut shares = Share::split(&privkey, 5, 3).unwrap();
+        shares[1].data[0] ^= 1;
+        assert!(Share::combine(&shares[..3]).is_err());
+        assert_eq!(Share::combine(&shares[..4]).unwrap().bytes(), privkey.bytes());
+
+        // A different byte, so the two errors cannot cancel out
+        shares[2].data[5] ^= 1;
+        assert!(Share::combine(&shares[..4]).is_err());
+        assert_eq!(Share::combine(&shares).unwrap().bytes(), privkey.bytes());
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let shares = Share::split(&test_key(), 3, 2).unwrap();
+        let encoded = shares[0].encode();
+        // Change the last hex digit of the data
+        let data_end = encoded.rfind(':').unwrap() - 1;
+        let mut damaged = encoded.to_string();
+        let digit = if &damaged[data_end..=data_end] == "0" { "1" } else { "0" };
+        damaged.replace_range(data_end..=data_end, digit);
+
+        let error = damaged.parse::<Share>().err().unwrap();
+        assert!(error.to_string().contains("checksum"));
+        assert!(encoded.parse::<Share>().is_ok());
+    }
+}
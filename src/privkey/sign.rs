@@ -0,0 +1,232 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use clap::ValueEnum;
+use cosmrs::{AccountId, Any, Coin};
+use cosmrs::bank::MsgSend;
+use cosmrs::distribution::MsgWithdrawDelegatorReward;
+use cosmrs::staking::MsgDelegate;
+use cosmrs::tendermint::chain::Id as ChainId;
+use cosmrs::tx::{Body, Fee, Msg, SignDoc, SignerInfo};
+use crate::privkey::PrivKey;
+use eyre::eyre;
+use eyre::Result;
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Fees and amounts are in unom
+pub const DENOM: &str = "unom";
+
+/// A message to sign, addresses are bech32 and amounts in unom
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TxMessage {
+    Delegate { validator: String, amount: u64 },
+    /// Withdraws the rewards from each validator
+    Claim { validators: Vec<String> },
+    Send { to: String, amount: u64 },
+}
+
+fn account_id(address: &str) -> Result<AccountId> {
+    address.parse()
+        .map_err(|e| eyre!("Invalid address {}: {}", address, e))
+}
+
+fn coin(amount: u64) -> Result<Coin> {
+    Coin::new(amount as u128, DENOM)
+        .map_err(|e| eyre!("Invalid amount {}{}: {}", amount, DENOM, e))
+}
+
+impl TxMessage {
+
+    /// The messages as protobuf `Any`, signed by `signer`
+    fn to_any(&self, signer: &AccountId) -> Result<Vec<Any>> {
+        let to_any = |msg: Result<Any, _>| msg.map_err(|e| eyre!("Failed to encode message: {}", e));
+        match self {
+            TxMessage::Delegate { validator, amount } => Ok(vec![to_any(MsgDelegate {
+                delegator_address: signer.clone(),
+                validator_address: account_id(validator)?,
+                amount:            coin(*amount)?,
+            }.to_any())?]),
+            TxMessage::Claim { validators } => validators.iter()
+                .map(|validator| to_any(MsgWithdrawDelegatorReward {
+                    delegator_address: signer.clone(),
+                    validator_address: account_id(validator)?,
+                }.to_any()))
+                .collect(),
+            TxMessage::Send { to, amount } => Ok(vec![to_any(MsgSend {
+                from_address: signer.clone(),
+                to_address:   account_id(to)?,
+                amount:       vec![coin(*amount)?],
+            }.to_any())?]),
+        }
+    }
+}
+
+/// Everything besides the messages that goes into the sign doc
+#[derive(Clone, Debug, Serialize)]
+pub struct TxParams {
+    pub chain_id:       String,
+    pub account_number: u64,
+    /// The account sequence, the nonce of the next transaction
+    pub sequence:       u64,
+    /// unom
+    pub fee:            u64,
+    pub gas_limit:      u64,
+    pub memo:           String,
+}
+
+/// A signed transaction ready to broadcast, `tx_bytes` is the encoded `TxRaw`
+#[derive(Clone, Debug, Serialize)]
+pub struct SignedTx {
+    pub signer:   String,
+    #[serde(flatten)]
+    pub params:   TxParams,
+    pub messages: Vec<TxMessage>,
+    #[serde(serialize_with = "as_base64")]
+    pub tx_bytes: Vec<u8>,
+}
+
+fn as_base64<S: serde::Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&BASE64.encode(bytes))
+}
+
+impl SignedTx {
+
+    /// Builds and signs the transaction in `SIGN_MODE_DIRECT`, without contacting a node.
+    /// secp256k1 signatures are deterministic (RFC 6979), the same key and inputs always
+    /// give the same bytes.
+    pub fn sign(privkey: &PrivKey, messages: Vec<TxMessage>, params: TxParams) -> Result<Self> {
+        if messages.is_empty() {
+            return Err(eyre!("Nothing to sign"));
+        }
+        let signer = privkey.account_id()?;
+
+        let mut msgs = Vec::new();
+        for message in &messages {
+            msgs.extend(message.to_any(signer)?);
+        }
+        let body = Body::new(msgs, params.memo.clone(), 0u16);
+
+        let auth_info = SignerInfo::single_direct(Some(*privkey.public_key()?), params.sequence)
+            .auth_info(Fee::from_amount_and_gas(coin(params.fee)?, params.gas_limit));
+
+        let chain_id = ChainId::from_str(&params.chain_id)
+            .map_err(|e| eyre!("Invalid chain id {}: {}", params.chain_id, e))?;
+        let sign_doc = SignDoc::new(&body, &auth_info, &chain_id, params.account_number)
+            .map_err(|e| eyre!("Failed to build sign doc: {}", e))?;
+        let tx_bytes = sign_doc.sign(privkey.signing_key()?)
+            .and_then(|raw| raw.to_bytes())
+            .map_err(|e| eyre!("Failed to sign transaction: {}", e))?;
+
+        Ok(Self {
+            signer: signer.to_string(),
+            params,
+            messages,
+            tx_bytes,
+        })
+    }
+
+    pub fn print(&self, format: Option<OutputFormat>) -> Result<()> {
+        match format {
+            Some(OutputFormat::Hex) => println!("{}", hex::encode(&self.tx_bytes)),
+            Some(OutputFormat::Base64) | None => println!("{}", BASE64.encode(&self.tx_bytes)),
+            Some(OutputFormat::Json) => println!("{}", serde_json::to_string(self)?),
+            Some(OutputFormat::JsonPretty) => println!("{}", serde_json::to_string_pretty(self)?),
+        }
+        Ok(())
+    }
+}
+
+/// Enum to represent output formats
+#[derive(Debug, Clone, ValueEnum)]
+pub enum OutputFormat {
+    Base64,
+    Hex,
+    Json,
+    JsonPretty,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "base64"      => Ok(OutputFormat::Base64),
+            "hex"         => Ok(OutputFormat::Hex),
+            "json"        => Ok(OutputFormat::Json),
+            "json-pretty" => Ok(OutputFormat::JsonPretty),
+            _ => Err(format!("Invalid output format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            OutputFormat::Base64     => "base64",
+            OutputFormat::Hex        => "hex",
+            OutputFormat::Json       => "json",
+            OutputFormat::JsonPretty => "json-pretty",
+        };
+        write!(f, "{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::privkey::key::test_key;
+
+    // Expected bytes come from a separate protobuf encoding of the same transactions,
+    // signed with RFC 6979 and a low S, the way k256 signs
+    const SIGNER: &str = "nomic1tp7fhly84qm6q4hhzmp0nh5frtdugmysqjs0rr";
+    const VALIDATOR_1: &str = "nomic1zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3a9ycp9";
+    const VALIDATOR_2: &str = "nomic1yg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zjcp7fj";
+    const RECIPIENT: &str = "nomic1xvenxvenxvenxvenxvenxvenxvenxvenqxkruq";
+
+    fn params(sequence: u64, gas_limit: u64, memo: &str) -> TxParams {
+        TxParams {
+            chain_id:       "nomic-stakenet-3".to_string(),
+            account_number: 12,
+            sequence,
+            fee:            10_000,
+            gas_limit,
+            memo:           memo.to_string(),
+        }
+    }
+
+    /// The signature is the last field of the `TxRaw`
+    fn signature(tx: &SignedTx) -> String {
+        BASE64.encode(&tx.tx_bytes[tx.tx_bytes.len() - 64..])
+    }
+
+    #[test]
+    fn delegate() {
+        let messages = vec![TxMessage::Delegate { validator: VALIDATOR_1.to_string(), amount: 5_000_000 }];
+        let tx = SignedTx::sign(&test_key(), messages, params(7, 200_000, "")).unwrap();
+        assert_eq!(tx.signer, SIGNER);
+        assert_eq!(BASE64.encode(&tx.tx_bytes), "CpcBCpQBCiMvY29zbW9zLnN0YWtpbmcudjFiZXRhMS5Nc2dEZWxlZ2F0ZRJtCixub21pYzF0cDdmaGx5ODRxbTZxNGhoem1wMG5oNWZydGR1Z215c3FqczBychIsbm9taWMxenlnM3p5ZzN6eWczenlnM3p5ZzN6eWczenlnM3p5ZzNhOXljcDkaDwoEdW5vbRIHNTAwMDAwMBJnClAKRgofL2Nvc21vcy5jcnlwdG8uc2VjcDI1NmsxLlB1YktleRIjCiEChL91YiYrvWlACFdI875q+lKuMXFVGB7OMbZjUcz/pLASBAoCCAEYBxITCg0KBHVub20SBTEwMDAwEMCaDBpATjFf8zWWuuAls8ASCa/u6vTDkm2Eh6C0SMRoBBWMu8N+LRvGXMKiIDKnfmmjEb9MA6n7YPA6RLfMEW/q2fi4Tw==");
+        assert_eq!(signature(&tx), "TjFf8zWWuuAls8ASCa/u6vTDkm2Eh6C0SMRoBBWMu8N+LRvGXMKiIDKnfmmjEb9MA6n7YPA6RLfMEW/q2fi4Tw==");
+    }
+
+    #[test]
+    fn claim() {
+        let messages = vec![TxMessage::Claim { validators: vec![VALIDATOR_1.to_string(), VALIDATOR_2.to_string()] }];
+        let tx = SignedTx::sign(&test_key(), messages, params(8, 300_000, "")).unwrap();
+        assert_eq!(BASE64.encode(&tx.tx_bytes), "CrQCCpcBCjcvY29zbW9zLmRpc3RyaWJ1dGlvbi52MWJldGExLk1zZ1dpdGhkcmF3RGVsZWdhdG9yUmV3YXJkElwKLG5vbWljMXRwN2ZobHk4NHFtNnE0aGh6bXAwbmg1ZnJ0ZHVnbXlzcWpzMHJyEixub21pYzF6eWczenlnM3p5ZzN6eWczenlnM3p5ZzN6eWczenlnM2E5eWNwOQqXAQo3L2Nvc21vcy5kaXN0cmlidXRpb24udjFiZXRhMS5Nc2dXaXRoZHJhd0RlbGVnYXRvclJld2FyZBJcCixub21pYzF0cDdmaGx5ODRxbTZxNGhoem1wMG5oNWZydGR1Z215c3FqczBychIsbm9taWMxeWczenlnM3p5ZzN6eWczenlnM3p5ZzN6eWczenlnM3pqY3A3ZmoSZwpQCkYKHy9jb3Ntb3MuY3J5cHRvLnNlY3AyNTZrMS5QdWJLZXkSIwohAoS/dWImK71pQAhXSPO+avpSrjFxVRgezjG2Y1HM/6SwEgQKAggBGAgSEwoNCgR1bm9tEgUxMDAwMBDgpxIaQA1KgNruwO0/sbgV0/7IY6YDXV9rqx+8IeMgFAVJmEaHCzQ6MKv+LJSmHoVaew7i7VeE85fO7qRi3pR5C38XL2I=");
+        assert_eq!(signature(&tx), "DUqA2u7A7T+xuBXT/shjpgNdX2urH7wh4yAUBUmYRocLNDowq/4slKYehVp7DuLtV4Tzl87upGLelHkLfxcvYg==");
+    }
+
+    #[test]
+    fn send() {
+        let messages = vec![TxMessage::Send { to: RECIPIENT.to_string(), amount: 1_234_567 }];
+        let tx = SignedTx::sign(&test_key(), messages, params(9, 200_000, "rent")).unwrap();
+        assert_eq!(BASE64.encode(&tx.tx_bytes), "CpYBCo0BChwvY29zbW9zLmJhbmsudjFiZXRhMS5Nc2dTZW5kEm0KLG5vbWljMXRwN2ZobHk4NHFtNnE0aGh6bXAwbmg1ZnJ0ZHVnbXlzcWpzMHJyEixub21pYzF4dmVueHZlbnh2ZW54dmVueHZlbnh2ZW54dmVueHZlbnF4a3J1cRoPCgR1bm9tEgcxMjM0NTY3EgRyZW50EmcKUApGCh8vY29zbW9zLmNyeXB0by5zZWNwMjU2azEuUHViS2V5EiMKIQKEv3ViJiu9aUAIV0jzvmr6Uq4xcVUYHs4xtmNRzP+ksBIECgIIARgJEhMKDQoEdW5vbRIFMTAwMDAQwJoMGkCgN4QP1Zq9l5y2SccsOz5029QLSB0WWZ4IEIf9zHssg2KeN/0TpmKQkDJpGtVaHfFnPLPCeM6LHmvvwp0Kig0b");
+        assert_eq!(signature(&tx), "oDeED9WavZectknHLDs+dNvUC0gdFlmeCBCH/cx7LINinjf9E6ZikJAyaRrVWh3xZzyzwnjOix5r78KdCooNGw==");
+    }
+
+    #[test]
+    fn nothing_to_sign() {
+        assert!(SignedTx::sign(&test_key(), vec![], params(0, 200_000, "")).is_err());
+    }
+}
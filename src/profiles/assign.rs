@@ -0,0 +1,168 @@
+use crate::functions::TableColumns;
+use crate::profiles::ConfigValidator;
+use crate::profiles::ProfileCollection;
+use crate::validators::Validator;
+use crate::validators::ValidatorCollection;
+use eyre::eyre;
+use eyre::Result;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+use tabled::builder::Builder;
+use tabled::settings::{Alignment, Border, Modify, Style};
+use tabled::settings::object::{Columns, Rows};
+
+/// Rules for picking validators
+#[derive(Clone, Debug)]
+pub struct AssignRules {
+    /// Validators to assign to each profile
+    pub count:            usize,
+    /// Skip the validators that together hold this percentage of voting power
+    pub skip_top_percent: f64,
+    /// Lowest rank number allowed, 1 allows every rank
+    pub min_rank:         u64,
+    /// Allow validators already configured in other profiles
+    pub allow_shared:     bool,
+    /// Replace the validators in the config instead of adding to them
+    pub replace:          bool,
+}
+
+/// The validators picked for one profile
+#[derive(Clone, Debug)]
+pub struct Assignment {
+    pub profile:    String,
+    pub address:    String,
+    pub validators: Vec<Validator>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ValidatorAssignment {
+    pub assignments: Vec<Assignment>,
+    pub rules:       AssignRules,
+}
+
+impl ValidatorAssignment {
+
+    /// Picks validators at random for the profiles with the given `targets` addresses.
+    /// Profiles in `collection` that are not targeted keep their validators, which are
+    /// excluded from the selection unless `allow_shared`.
+    pub fn plan(
+        collection: &ProfileCollection,
+        validators: &ValidatorCollection,
+        targets:    &[String],
+        rules:      AssignRules,
+    ) -> Result<Self> {
+        if rules.count == 0 {
+            return Err(eyre!("Count must be greater than 0"));
+        }
+
+        let eligible = validators.skip_top_voting_power(rules.skip_top_percent)?;
+        let mut pool: Vec<&Validator> = eligible.iter()
+            .filter(|v| v.rank() >= rules.min_rank)
+            .collect();
+        pool.shuffle(&mut rand::thread_rng());
+
+        let mut profiles: Vec<_> = collection.iter()
+            .filter(|p| targets.iter().any(|t| t == p.address()))
+            .collect();
+        profiles.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let mut used: HashSet<String> = HashSet::new();
+        if !rules.allow_shared {
+            for profile in collection.iter() {
+                if rules.replace && targets.iter().any(|t| t == profile.address()) {
+                    continue;
+                }
+                used.extend(profile.config().validators.iter().map(|v| v.address.clone()));
+            }
+        }
+
+        let mut assignments = Vec::new();
+        for profile in profiles {
+            let own: HashSet<&str> = if rules.replace {
+                HashSet::new()
+            } else {
+                profile.config().validators.iter().map(|v| v.address.as_str()).collect()
+            };
+
+            let validators: Vec<Validator> = pool.iter()
+                .filter(|v| !used.contains(v.address()) && !own.contains(v.address()))
+                .take(rules.count)
+                .copied()
+                .cloned()
+                .collect();
+
+            if validators.len() < rules.count {
+                eprintln!(
+                    "Warning: only {} of {} validators could be assigned to {}",
+                    validators.len(), rules.count, profile.name(),
+                );
+            }
+            if !rules.allow_shared {
+                used.extend(validators.iter().map(|v| v.address().to_string()));
+            }
+
+            assignments.push(Assignment {
+                profile: profile.name().to_string(),
+                address: profile.address().to_string(),
+                validators,
+            });
+        }
+
+        Ok(Self { assignments, rules })
+    }
+
+    pub fn table(&self) -> String {
+        let mut rows: Vec<TableColumns> = Vec::new();
+        rows.push(TableColumns::new(vec![
+            "Profile", "Rank", "Validator Address", "Moniker", "Voting Power",
+        ]));
+        for assignment in &self.assignments {
+            if assignment.validators.is_empty() {
+                rows.push(TableColumns::new(vec![ assignment.profile.as_str(), "-", "none left", "", "" ]));
+            }
+            for (index, validator) in assignment.validators.iter().enumerate() {
+                rows.push(TableColumns::new(vec![
+                    if index == 0 { assignment.profile.as_str() } else { "" },
+                    &validator.rank().to_string(),
+                    validator.address(),
+                    validator.moniker(),
+                    &validator.voting_power_nom(),
+                ]));
+            }
+        }
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([
+                row.cell0.clone(), row.cell1.clone(), row.cell2.clone(),
+                row.cell3.clone(), row.cell4.clone(),
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::blank())
+            .with(Modify::new(Columns::single(1)).with(Alignment::right()))
+            .with(Modify::new(Columns::single(4)).with(Alignment::right()))
+            .with(Modify::new(Rows::first()).with(Border::new().set_bottom('-')))
+            ;
+
+        table.to_string()
+    }
+
+    /// Writes the picked validators into each profile's config
+    pub fn apply(&self, collection: &ProfileCollection) -> Result<()> {
+        for assignment in &self.assignments {
+            if assignment.validators.is_empty() {
+                continue;
+            }
+            let validators: Vec<ConfigValidator> = assignment.validators.iter()
+                .map(|v| ConfigValidator::new(v.address(), v.moniker()))
+                .collect();
+            collection.profile_by_address(&assignment.address)?
+                .assign_validators(&validators, self.rules.replace)?;
+            println!("{}: assigned {}", assignment.profile,
+                validators.iter().map(|v| v.name.as_str()).collect::<Vec<_>>().join(", "));
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
 
+use crate::cache;
 use crate::global::CONFIG;
+use crate::global::Runtime;
 use eyre::eyre;
 use eyre::Result;
+use serde::{Deserialize, Serialize};
 use std::process::Command;
 use chrono::{Utc, DateTime};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct Balance {
     pub address: String,
@@ -33,6 +36,19 @@ impl Balance {
         }
     }
 
+    /// Fetches and caches the balance, or in offline mode uses the last one fetched
+    pub fn load(address: &str) -> Result<Self> {
+        let key = format!("balance-{}", address);
+        if Runtime::offline() {
+            return cache::load::<Self>(&key)
+                .map(|cached| cached.value)
+                .ok_or_else(|| eyre!("No cached balance for {}", address));
+        }
+        let balance = Self::fetch(Some(address))?;
+        cache::store(&key, &balance);
+        Ok(balance)
+    }
+
     /// Fetches the balance from the command output and returns a new Balance instance.
     pub fn fetch(address: Option<&str>) -> Result<Self> {
         let timestamp = Some(Utc::now());
@@ -0,0 +1,417 @@
+use crate::amount::Amount;
+use crate::functions::NumberDisplay;
+use crate::functions::TableColumns;
+use crate::profiles::Compound;
+use crate::profiles::Config;
+use std::cmp::max;
+use tabled::builder::Builder;
+use tabled::settings::{Alignment, Color, Modify, Span, Style};
+use tabled::settings::object::{Cell, Columns};
+
+/// Everything the staking calculation needs, gathered up front.
+///
+/// `Profile::calc_input` fills this from the chain and the profile config,
+/// the `calc` subcommand fills it from the command line. All amounts are in unom.
+#[derive(Clone, Debug)]
+pub struct CalcInput {
+    /// Wallet balance
+    pub balance:      u64,
+    /// Unclaimed rewards across all delegations
+    pub liquid:       u64,
+    /// Staked across all validators
+    pub total_staked: u64,
+    /// Staked with the validator we are delegating to
+    pub staked:       u64,
+    /// Estimated daily reward
+    pub daily_reward: u64,
+    pub claim_fee:    u64,
+    pub stake_fee:    u64,
+    pub config:       Config,
+    /// Explicit quantity to stake, `None` to calculate it
+    pub quantity:     Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Calc {
+    pub minimum_balance:          u64,
+    pub minimum_stake:            u64,
+    pub available_without_claim:  u64,
+    pub available_after_claim:    u64,
+    pub available:                u64,
+    pub remainder:                u64,
+    pub needed:                   u64,
+    pub can_stake_without_claim:  bool,
+    pub can_stake_after_claim:    bool,
+    pub remaining:                u64,
+    pub needs_claim:              bool,
+    pub quantity:                 u64,
+}
+
+/// Minimum wallet balance to keep, `minimum_balance_ratio` of the total staked,
+/// rounded down to 10_000 unom (0.01 nom) and never below `config.minimum_balance`.
+/// The ratio is scaled by 1_000_000 and clamped to 1.
+pub fn minimum_balance(total_staked: u64, config: &Config) -> u64 {
+    let adjusted_ratio = config.minimum_balance_ratio.min(1_000_000);
+
+    total_staked
+        .saturating_mul(adjusted_ratio)   // Apply ratio to staked amount
+        .saturating_div(1_000_000)        // Scale down to the original ratio (0-1)
+        .saturating_div(10_000)           // Round down to nearest 10_000 unom
+        .saturating_mul(10_000)           // Ensure 10_000 unom increments
+        .max(config.minimum_balance)      // Ensure result meets or exceeds config minimum
+}
+
+/// Quantity that triggers a delegation.
+///
+/// - `optimize_minimum_stake`: the fee-optimal compounding threshold,
+///   falling back to `config.minimum_stake` if it cannot be determined.
+/// - `adjust_minimum_stake`: the daily reward rounded to `minimum_stake_rounding`,
+///   never below `config.minimum_stake`.
+/// - otherwise `config.minimum_stake`.
+pub fn minimum_stake(
+    total_staked: u64,
+    daily_reward: u64,
+    claim_fee:    u64,
+    stake_fee:    u64,
+    config:       &Config,
+) -> u64 {
+    let config_min = config.minimum_stake;
+    let rounding   = config.minimum_stake_rounding;
+
+    if config.optimize_minimum_stake {
+        let compound = Compound::new(total_staked, daily_reward, claim_fee, stake_fee, rounding);
+        match compound.optimal_minimum_stake() {
+            0 => config_min,
+            optimal => optimal,
+        }
+    } else if config.adjust_minimum_stake && rounding > 0 {
+        let daily = daily_reward.saturating_add(rounding.saturating_div(2));
+        max(config_min, daily.saturating_sub(daily % rounding))
+    } else {
+        config_min
+    }
+}
+
+/// Works out what can be staked, whether rewards need to be claimed first,
+/// and how much is still missing. Performs no lookups and no I/O.
+pub fn calc(input: &CalcInput) -> Calc {
+    let minimum_balance = minimum_balance(input.total_staked, &input.config);
+    let minimum_stake = minimum_stake(
+        input.total_staked,
+        input.daily_reward,
+        input.claim_fee,
+        input.stake_fee,
+        &input.config,
+    );
+
+    let available_without_claim = input.balance
+        .saturating_sub(minimum_balance)
+        .saturating_sub(input.stake_fee);
+
+    let available_after_claim = input.balance
+        .saturating_add(input.liquid)
+        .saturating_sub(minimum_balance)
+        .saturating_sub(input.claim_fee)
+        .saturating_sub(input.stake_fee);
+
+    // A zero minimum stake means every amount is a whole multiple
+    let remainder = input.staked.checked_rem(minimum_stake).unwrap_or(0);
+    let needed = input.quantity.unwrap_or_else(|| minimum_stake.saturating_sub(remainder));
+    let can_stake_without_claim = available_without_claim > needed;
+    let can_stake_after_claim = available_after_claim > needed;
+    let remaining = if can_stake_without_claim || can_stake_after_claim {
+        0
+    } else {
+        needed.saturating_sub(available_after_claim)
+    };
+    let needs_claim = !can_stake_without_claim && can_stake_after_claim;
+
+    // Calculate available funds based on conditions
+    let available = if can_stake_without_claim {
+        available_without_claim
+    } else if can_stake_after_claim {
+        available_after_claim
+    } else {
+        0
+    };
+
+    // Stake what is needed plus as many whole minimum stakes as are available
+    let quantity = input.quantity.unwrap_or_else(|| {
+        let extra = available.saturating_sub(needed);
+        let extra = match minimum_stake {
+            0 => extra,
+            minimum_stake => extra.saturating_div(minimum_stake).saturating_mul(minimum_stake),
+        };
+        extra.saturating_add(needed)
+    });
+
+    Calc {
+        minimum_balance,
+        minimum_stake,
+        available_without_claim,
+        available_after_claim,
+        available,
+        remainder,
+        needed,
+        can_stake_without_claim,
+        can_stake_after_claim,
+        remaining,
+        needs_claim,
+        quantity,
+    }
+}
+
+/// Resolves `amount` to a quantity to stake, `all` and `50%` being
+/// taken from what can be staked after claiming rewards. Staking requires
+/// more than the quantity to be available, so `all` leaves 1 unom behind.
+pub fn resolve_quantity(input: &CalcInput, amount: Amount) -> u64 {
+    match amount {
+        Amount::Unom(n) => n,
+        _ => {
+            let mut input = input.clone();
+            input.quantity = None;
+            amount.resolve(calc(&input).available_after_claim.saturating_sub(1))
+        },
+    }
+}
+
+impl Calc {
+
+    pub fn table(&self) -> String {
+        let nom = |n: u64| NumberDisplay::new(n).scale(6).decimal_places(6).trim(true).format();
+
+        let rows = vec![
+            TableColumns::new(vec![ "Calculation:" ]),
+            TableColumns::new(vec![ "Minimum Balance:",          &nom(self.minimum_balance) ]),
+            TableColumns::new(vec![ "Minimum Stake:",            &nom(self.minimum_stake) ]),
+            TableColumns::new(vec![ "Available without Claim:",  &nom(self.available_without_claim) ]),
+            TableColumns::new(vec![ "Available after Claim:",    &nom(self.available_after_claim) ]),
+            TableColumns::new(vec![ "Validator Remainder:",      &nom(self.remainder) ]),
+            TableColumns::new(vec![ "Needed:",                   &nom(self.needed) ]),
+            TableColumns::new(vec![ "Remaining:",                &nom(self.remaining) ]),
+            TableColumns::new(vec![ "Can Stake without Claim:",  &self.can_stake_without_claim.to_string() ]),
+            TableColumns::new(vec![ "Can Stake after Claim:",    &self.can_stake_after_claim.to_string() ]),
+            TableColumns::new(vec![ "Needs Claim:",              &self.needs_claim.to_string() ]),
+            TableColumns::new(vec![ "Quantity:",                 &nom(self.quantity) ]),
+        ];
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([row.cell0.clone(), row.cell1.clone()]);
+        }
+
+        let mut table = builder.build();
+        table
+            .with(Style::empty())
+            .with(Modify::new(Columns::single(1)).with(Alignment::right()).with(Color::FG_BLUE))
+            .with(Modify::new(Cell::new(0, 0)).with(Span::column(2)))
+            .with(Modify::new(Cell::new(0, 0)).with(Color::new("\x1b[1m", "\x1b[0m")))
+            .with(Modify::new(Cell::new(rows.len() - 1, 1)).with(Color::FG_GREEN))
+            ;
+
+        table.to_string()
+    }
+}
+
+impl std::fmt::Display for Calc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.table())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            profile:                String::new(),
+            minimum_balance:        1_000_000,
+            minimum_balance_ratio:  0,
+            minimum_stake:          5_000_000,
+            adjust_minimum_stake:   false,
+            optimize_minimum_stake: false,
+            minimum_stake_rounding: 1_000_000,
+            daily_reward:           0,
+            tags:                   Vec::new(),
+            validators:             Vec::new(),
+        }
+    }
+
+    fn input(balance: u64, liquid: u64) -> CalcInput {
+        CalcInput {
+            balance,
+            liquid,
+            total_staked: 0,
+            staked:       0,
+            daily_reward: 0,
+            claim_fee:    10_000,
+            stake_fee:    10_000,
+            config:       config(),
+            quantity:     None,
+        }
+    }
+
+    #[test]
+    fn zero_balance_cannot_stake() {
+        let result = calc(&input(0, 0));
+        assert_eq!(result.available_without_claim, 0);
+        assert_eq!(result.available_after_claim, 0);
+        assert!(!result.can_stake_without_claim);
+        assert!(!result.can_stake_after_claim);
+        assert!(!result.needs_claim);
+        assert_eq!(result.available, 0);
+        assert_eq!(result.needed, 5_000_000);
+        assert_eq!(result.remaining, 5_000_000);
+    }
+
+    #[test]
+    fn balance_equal_to_threshold_cannot_stake() {
+        // minimum balance + stake fee + minimum stake
+        let result = calc(&input(6_010_000, 0));
+        assert_eq!(result.available_without_claim, result.needed);
+        assert!(!result.can_stake_without_claim);
+        assert!(!result.can_stake_after_claim);
+        assert_eq!(result.remaining, 10_000);
+
+        let result = calc(&input(6_010_001, 0));
+        assert!(result.can_stake_without_claim);
+        assert!(!result.needs_claim);
+        assert_eq!(result.remaining, 0);
+        assert_eq!(result.quantity, 5_000_000);
+    }
+
+    #[test]
+    fn needs_claim_when_only_rewards_cover_the_stake() {
+        let result = calc(&input(3_000_000, 4_000_000));
+        assert_eq!(result.available_without_claim, 1_990_000);
+        assert_eq!(result.available_after_claim, 5_980_000);
+        assert!(!result.can_stake_without_claim);
+        assert!(result.can_stake_after_claim);
+        assert!(result.needs_claim);
+        assert_eq!(result.available, 5_980_000);
+        assert_eq!(result.quantity, 5_000_000);
+    }
+
+    #[test]
+    fn no_claim_when_balance_covers_the_stake() {
+        let result = calc(&input(20_000_000, 4_000_000));
+        assert!(result.can_stake_without_claim);
+        assert!(!result.needs_claim);
+        assert_eq!(result.available, 18_990_000);
+        // needed plus two whole minimum stakes
+        assert_eq!(result.quantity, 15_000_000);
+    }
+
+    #[test]
+    fn remainder_reduces_needed() {
+        let mut input = input(20_000_000, 0);
+        input.staked = 7_000_000;
+        let result = calc(&input);
+        assert_eq!(result.remainder, 2_000_000);
+        assert_eq!(result.needed, 3_000_000);
+        assert_eq!(result.quantity, 18_000_000);
+    }
+
+    #[test]
+    fn zero_minimum_stake_does_not_divide_by_zero() {
+        let mut input = input(2_000_000, 0);
+        input.staked = 7_000_000;
+        input.config.minimum_stake = 0;
+        let result = calc(&input);
+        assert_eq!(result.remainder, 0);
+        assert_eq!(result.needed, 0);
+        assert_eq!(result.quantity, 990_000);
+    }
+
+    #[test]
+    fn minimum_balance_follows_ratio() {
+        let mut config = config();
+        config.minimum_balance_ratio = 1_000; // 0.001
+        assert_eq!(minimum_balance(100_000_000_000, &config), 100_000_000);
+        // Rounded down to 10_000 unom
+        assert_eq!(minimum_balance(1_234_567_890_000, &config), 1_234_560_000);
+        // Never below the configured minimum
+        assert_eq!(minimum_balance(0, &config), 1_000_000);
+    }
+
+    #[test]
+    fn minimum_stake_rounding() {
+        let mut config = config();
+        config.minimum_stake = 1_000_000;
+        config.adjust_minimum_stake = true;
+
+        assert_eq!(minimum_stake(0, 1_499_999, 0, 0, &config), 1_000_000);
+        assert_eq!(minimum_stake(0, 1_500_000, 0, 0, &config), 2_000_000);
+        assert_eq!(minimum_stake(0, 2_499_999, 0, 0, &config), 2_000_000);
+        // Never below the configured minimum
+        assert_eq!(minimum_stake(0, 0, 0, 0, &config), 1_000_000);
+
+        // No rounding, no adjustment
+        config.minimum_stake_rounding = 0;
+        assert_eq!(minimum_stake(0, 1_500_000, 0, 0, &config), 1_000_000);
+    }
+
+    #[test]
+    fn optimized_minimum_stake_falls_back_to_config() {
+        let mut config = config();
+        config.optimize_minimum_stake = true;
+        assert_eq!(minimum_stake(0, 0, 10_000, 10_000, &config), 5_000_000);
+    }
+
+    #[test]
+    fn minimum_balance_saturates() {
+        let mut config = config();
+        // Ratios above 1 are clamped
+        config.minimum_balance_ratio = 2_000_000;
+        assert_eq!(minimum_balance(u64::MAX, &config), 18_446_744_070_000);
+    }
+
+    #[test]
+    fn minimum_stake_saturates() {
+        let mut config = config();
+        config.adjust_minimum_stake = true;
+        assert_eq!(minimum_stake(0, u64::MAX, 0, 0, &config), 18_446_744_073_709_000_000);
+    }
+
+    #[test]
+    fn calc_saturates() {
+        let result = calc(&input(u64::MAX, u64::MAX));
+        assert_eq!(result.available_without_claim, u64::MAX - 1_010_000);
+        assert_eq!(result.available_after_claim, u64::MAX - 1_020_000);
+        assert!(result.can_stake_without_claim);
+        assert_eq!(result.quantity % 5_000_000, 0);
+    }
+
+    #[test]
+    fn explicit_quantity_saturates() {
+        let mut input = input(u64::MAX, u64::MAX);
+        input.quantity = Some(u64::MAX);
+        let result = calc(&input);
+        assert!(!result.can_stake_without_claim);
+        assert!(!result.can_stake_after_claim);
+        assert_eq!(result.remaining, 1_020_000);
+    }
+
+    #[test]
+    fn fees_above_balance_saturate_to_zero() {
+        let mut input = input(0, 0);
+        input.claim_fee = u64::MAX;
+        input.stake_fee = u64::MAX;
+        let result = calc(&input);
+        assert_eq!(result.available_without_claim, 0);
+        assert_eq!(result.available_after_claim, 0);
+    }
+
+    #[test]
+    fn all_resolves_to_a_stakeable_quantity() {
+        let mut input = input(3_000_000, 4_000_000);
+        let quantity = resolve_quantity(&input, Amount::All);
+        assert_eq!(quantity, 5_979_999);
+
+        input.quantity = Some(quantity);
+        let result = calc(&input);
+        assert!(result.can_stake_after_claim);
+        assert_eq!(result.quantity, quantity);
+    }
+}
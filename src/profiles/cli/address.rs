@@ -1,4 +1,3 @@
-
 use clap::Args;
 use crate::profiles::ProfileCollection;
 use eyre::Result;
@@ -8,12 +7,23 @@ use eyre::Result;
 pub struct Command {
     #[arg()]
     profile: Option<String>,
+
+    /// Also list the linked addresses on the chains in the global address_prefixes
+    #[arg(long, short)]
+    linked: bool,
 }
 
 impl Command {
     pub fn run(&self) -> Result<()> {
         let profile = ProfileCollection::new()?
             .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?;
-        Ok(println!("{}", profile.address()))
+        if !self.linked {
+            return Ok(println!("{}", profile.address()));
+        }
+        println!("{:<10} {}", "nomic", profile.address());
+        for (prefix, address) in profile.linked_addresses()? {
+            println!("{:<10} {}", prefix, address);
+        }
+        Ok(())
     }
 }
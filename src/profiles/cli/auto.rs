@@ -1,14 +1,24 @@
-
 use clap::Args;
+use crate::profiles::Exposure;
 use crate::profiles::ProfileCollection;
 use eyre::Result;
 
 #[derive(Debug, Args)]
 #[command(about = "Auto delegate all profiles")]
-pub struct Command;
+pub struct Command {
+    /// Only profiles with this tag
+    #[arg(short, long, visible_alias = "group")]
+    tag: Option<String>,
+}
 
 impl Command {
     pub fn run(&self) -> Result<()> {
-        ProfileCollection::load()?.auto_delegate()
+        let mut collection = ProfileCollection::load()?;
+        // Exposure is fleet-wide, so build it before narrowing down by tag
+        let mut exposure = Exposure::new(&collection);
+        if let Some(tag) = &self.tag {
+            collection.filter_tag(tag)?;
+        }
+        collection.auto_delegate(&mut exposure)
     }
 }
@@ -1,19 +1,39 @@
-
 use clap::Args;
+use crate::functions::NumberDisplay;
 use crate::profiles::ProfileCollection;
 use eyre::Result;
 
 #[derive(Debug, Args)]
 #[command(about = "Display the Balance for a profile")]
 pub struct Command {
-    #[arg()]
+    #[arg(conflicts_with = "tag")]
     profile: Option<String>,
+
+    /// All profiles with this tag
+    #[arg(short, long, visible_alias = "group")]
+    tag: Option<String>,
 }
 
 impl Command {
     pub fn run(&self) -> Result<()> {
-        let profile = ProfileCollection::new()?
-            .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?;
-        Ok(println!("{}", profile.balance()))
+        let mut collection = ProfileCollection::new()?;
+        match &self.tag {
+            Some(tag) => {
+                collection.filter_tag(tag)?;
+                collection.sort_by_name();
+                for profile in collection.iter() {
+                    println!("{:<16} {:>20}",
+                        profile.name(),
+                        NumberDisplay::new(*profile.balance()).scale(6).decimal_places(6).format(),
+                    );
+                }
+                Ok(())
+            },
+            None => {
+                let profile = collection
+                    .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?;
+                Ok(println!("{}", profile.balance()))
+            },
+        }
     }
 }
@@ -0,0 +1,123 @@
+use clap::Args;
+use crate::amount::Amount;
+use crate::functions::validate_ratio;
+use crate::global::CONFIG;
+use crate::profiles::calc;
+use crate::profiles::CalcInput;
+use crate::profiles::Config;
+use crate::profiles::ProfileCollection;
+use eyre::Result;
+
+#[derive(Debug, Args)]
+#[command(about = "Show what would be staked, using a profile's figures or hypothetical ones")]
+pub struct Command {
+    /// Profile to start from, otherwise every figure starts at 0 with the default config
+    #[arg()]
+    profile: Option<String>,
+
+    /// Validator address or moniker, defaults to the profile's active validator
+    #[arg(short = 'V', long)]
+    validator: Option<String>,
+
+    /// Wallet balance
+    #[arg(short = 'B', long)]
+    balance: Option<Amount>,
+
+    /// Unclaimed rewards
+    #[arg(short = 'l', long, aliases = ["rewards"])]
+    liquid: Option<Amount>,
+
+    /// Total staked across all validators
+    #[arg(short = 't', long, aliases = ["total"])]
+    total_staked: Option<Amount>,
+
+    /// Staked with the validator
+    #[arg(short = 'k', long)]
+    staked: Option<Amount>,
+
+    /// Daily reward
+    #[arg(short = 'd', long, aliases = ["daily"])]
+    daily_reward: Option<Amount>,
+
+    /// Claim fee
+    #[arg(long)]
+    claim_fee: Option<Amount>,
+
+    /// Stake fee
+    #[arg(long)]
+    stake_fee: Option<Amount>,
+
+    /// Minimum wallet balance
+    #[arg(short = 'b', long, aliases = ["min-bal", "mb"])]
+    minimum_balance: Option<Amount>,
+
+    /// Ratio of total staked to leave as wallet balance
+    #[arg(short = 'r', long, aliases = ["min-bal-ratio", "mbr"], value_parser = validate_ratio)]
+    minimum_balance_ratio: Option<f64>,
+
+    /// Minimum stake
+    #[arg(short = 's', long, aliases = ["min-stake", "ms"])]
+    minimum_stake: Option<Amount>,
+
+    /// Adjust minimum stake to daily reward
+    #[arg(short = 'j', long, aliases = ["adjust", "adj"])]
+    adjust_minimum_stake: Option<bool>,
+
+    /// Set minimum stake to the fee-optimal compounding threshold
+    #[arg(short = 'p', long, aliases = ["optimize", "opt"])]
+    optimize_minimum_stake: Option<bool>,
+
+    /// Minimum stake will be a multiple of this
+    #[arg(short = 'o', long, aliases = ["rounding", "round"])]
+    minimum_stake_rounding: Option<Amount>,
+
+    /// Quantity to stake instead of the calculated one (e.g. 1.5, 1500000unom, all, 50%)
+    #[arg(short = 'q', long)]
+    quantity: Option<Amount>,
+}
+
+impl Command {
+    pub fn run(&self) -> Result<()> {
+        let mut input = match &self.profile {
+            Some(profile) => {
+                ProfileCollection::new()?
+                    .profile_by_name_or_address_or_home(profile)?
+                    .calc_input(self.validator.as_deref(), None)
+            },
+            None => CalcInput {
+                balance:      0,
+                liquid:       0,
+                total_staked: 0,
+                staked:       0,
+                daily_reward: 0,
+                claim_fee:    CONFIG.claim_fee,
+                stake_fee:    CONFIG.stake_fee,
+                config:       Config::default(),
+                quantity:     None,
+            },
+        };
+
+        let unom = |amount: Option<Amount>| amount.map(|a| a.unom()).transpose();
+
+        if let Some(v) = unom(self.balance)?      { input.balance = v; }
+        if let Some(v) = unom(self.liquid)?       { input.liquid = v; }
+        if let Some(v) = unom(self.total_staked)? { input.total_staked = v; }
+        if let Some(v) = unom(self.staked)?       { input.staked = v; }
+        if let Some(v) = unom(self.daily_reward)? { input.daily_reward = v; }
+        if let Some(v) = unom(self.claim_fee)?    { input.claim_fee = v; }
+        if let Some(v) = unom(self.stake_fee)?    { input.stake_fee = v; }
+
+        let config = &mut input.config;
+        if let Some(v) = unom(self.minimum_balance)?        { config.minimum_balance = v; }
+        if let Some(v) = unom(self.minimum_stake)?          { config.minimum_stake = v; }
+        if let Some(v) = unom(self.minimum_stake_rounding)? { config.minimum_stake_rounding = v; }
+        if let Some(v) = self.minimum_balance_ratio         { config.minimum_balance_ratio = (v * 1_000_000.0).round() as u64; }
+        if let Some(v) = self.adjust_minimum_stake          { config.adjust_minimum_stake = v; }
+        if let Some(v) = self.optimize_minimum_stake        { config.optimize_minimum_stake = v; }
+
+        input.quantity = self.quantity.map(|amount| calc::resolve_quantity(&input, amount));
+
+        println!("\n{}\n", calc::calc(&input));
+        Ok(())
+    }
+}
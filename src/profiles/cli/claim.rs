@@ -1,19 +1,44 @@
-
 use clap::Args;
 use crate::profiles::ProfileCollection;
+use eyre::eyre;
 use eyre::Result;
 
 #[derive(Debug, Args)]
 #[command(about = "Claim staking rewards for a profile")]
 pub struct Command {
-    #[arg()]
+    #[arg(conflicts_with = "tag")]
     profile: Option<String>,
+
+    /// All profiles with this tag
+    #[arg(short, long, visible_alias = "group")]
+    tag: Option<String>,
 }
 
 impl Command {
     pub fn run(&self) -> Result<()> {
-        let mut profile = ProfileCollection::new()?
-            .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?;
-        profile.nomic_claim()
+        let mut collection = ProfileCollection::new()?;
+        match &self.tag {
+            Some(tag) => {
+                collection.filter_tag(tag)?;
+                collection.sort_by_name();
+                let mut failed = Vec::new();
+                for profile in collection.iter_mut() {
+                    if let Err(e) = profile.nomic_claim() {
+                        eprintln!("{}: failed to claim: {}", profile.name(), e);
+                        failed.push(profile.name().to_string());
+                    }
+                }
+                if failed.is_empty() {
+                    Ok(())
+                } else {
+                    Err(eyre!("Claim failed for: {}", failed.join(", ")))
+                }
+            },
+            None => {
+                let mut profile = collection
+                    .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?;
+                profile.nomic_claim()
+            },
+        }
     }
 }
@@ -1,6 +1,8 @@
 
 use clap::{Args, Parser, Subcommand};
+use crate::amount::Amount;
 use eyre::Result;
+use crate::profiles::EditConfig;
 use crate::profiles::ProfileCollection;
 use crate::functions::validate_ratio;
 
@@ -32,7 +34,7 @@ pub enum SetCommands {
     )]
     MinimumBalance {
         #[arg()]
-        minimum_balance: Option<f64>,
+        minimum_balance: Option<Amount>,
     },
 
     #[command(about = "Set the minimum stake",
@@ -40,7 +42,7 @@ pub enum SetCommands {
     )]
     MinimumStake {
         #[arg()]
-        minimum_stake: Option<f64>,
+        minimum_stake: Option<Amount>,
     },
 
     #[command(about = "Set the daily reward",
@@ -48,7 +50,7 @@ pub enum SetCommands {
     )]
     DailyReward {
         #[arg()]
-        daily_reward: Option<f64>,
+        daily_reward: Option<Amount>,
     },
 }
 
@@ -60,7 +62,7 @@ pub struct EditArgs {
         aliases = ["min-bal", "mb", "bal", "balance"],
         help = "Minimum wallet balance",
     )]
-    minimum_balance: Option<f64>,
+    minimum_balance: Option<Amount>,
 
     #[arg(
         short = 'r', long,
@@ -75,7 +77,7 @@ pub struct EditArgs {
         aliases = ["min-stake", "ms", "stk", "stake"],
         help = "Minimum stake",
     )]
-    minimum_stake: Option<f64>,
+    minimum_stake: Option<Amount>,
 
     #[arg(
         short = 'j',
@@ -85,19 +87,27 @@ pub struct EditArgs {
     )]
     adjust_minimum_stake: Option<bool>,
 
+    #[arg(
+        short = 'p',
+        long = "optimize-minimum-stake",
+        aliases = ["optimize-min-stake", "oms", "opt-stk", "optimize", "opt"],
+        help = "Set minimum stake to the fee-optimal compounding threshold"
+    )]
+    optimize_minimum_stake: Option<bool>,
+
     #[arg(
         short = 'o', long,
         aliases = ["min-stake-round", "msr", "rnd", "round", "rounding"],
         help = "Minimum stake will be a multiple of this"
     )]
-    minimum_stake_rounding: Option<f64>,
+    minimum_stake_rounding: Option<Amount>,
 
     #[arg(
         short = 'd', long,
         aliases = ["daily"],
         help = "Daily Reward",
     )]
-    daily_reward: Option<f64>,
+    daily_reward: Option<Amount>,
 
     #[arg(
         short = 'a', long,
@@ -120,6 +130,20 @@ pub struct EditArgs {
         help = "Remove a validator"
     )]
     remove_validator: Option<String>,
+
+    #[arg(
+        short = 't', long,
+        aliases = ["tag"],
+        help = "Add a tag (e.g. cold, ops, client-a)",
+    )]
+    add_tag: Option<String>,
+
+    #[arg(
+        short = 'T', long,
+        aliases = ["untag"],
+        help = "Remove a tag",
+    )]
+    remove_tag: Option<String>,
 }
 
 impl Cli {
@@ -133,17 +157,20 @@ impl Cli {
 
         match &self.command {
             Some(Command::Edit(args)) => {
-                profile.edit_config(
-                    args.minimum_balance.map(|v| (v * 1_000_000.0) as u64),
-                    args.minimum_balance_ratio.map(|v| (v * 1_000_000.0) as u64),
-                    args.minimum_stake.map(|v| (v * 1_000_000.0) as u64),
-                    args.adjust_minimum_stake,
-                    args.minimum_stake_rounding.map(|v| (v * 1_000_000.0) as u64),
-                    args.daily_reward.map(|v| (v * 1_000_000.0) as u64),
-                    args.add_validator.clone(),
-                    args.remove_validator.clone(),
-                    args.rotate_validators,
-                )?;
+                profile.edit_config(EditConfig {
+                    minimum_balance:        args.minimum_balance.map(|a| a.unom()).transpose()?,
+                    minimum_balance_ratio:  args.minimum_balance_ratio.map(|v| (v * 1_000_000.0).round() as u64),
+                    minimum_stake:          args.minimum_stake.map(|a| a.unom()).transpose()?,
+                    adjust_minimum_stake:   args.adjust_minimum_stake,
+                    optimize_minimum_stake: args.optimize_minimum_stake,
+                    minimum_stake_rounding: args.minimum_stake_rounding.map(|a| a.unom()).transpose()?,
+                    daily_reward:           args.daily_reward.map(|a| a.unom()).transpose()?,
+                    add_validator:          args.add_validator.clone(),
+                    remove_validator:       args.remove_validator.clone(),
+                    rotate_validators:      args.rotate_validators,
+                    add_tag:                args.add_tag.clone(),
+                    remove_tag:             args.remove_tag.clone(),
+                })?;
                 Ok(())
             }
 
@@ -151,16 +178,13 @@ impl Cli {
                 if let Some(cmd) = command {
                     match cmd {
                         SetCommands::MinimumBalance { minimum_balance } => {
-                            let bal = minimum_balance.map(|b| (b * 1_000_000.0) as u64);
-                            profile.set_config_minimum_balance(bal)
+                            profile.set_config_minimum_balance(*minimum_balance)
                         }
                         SetCommands::MinimumStake { minimum_stake } => {
-                            let stake = minimum_stake.map(|b| (b * 1_000_000.0) as u64);
-                            profile.set_config_minimum_stake(stake)
+                            profile.set_config_minimum_stake(*minimum_stake)
                         }
                         SetCommands::DailyReward { daily_reward } => {
-                            let reward = daily_reward.map(|b| (b * 1_000_000.0) as u64);
-                            profile.set_config_daily_reward(reward)
+                            profile.set_config_daily_reward(*daily_reward)
                         }
                     }
                 } else {
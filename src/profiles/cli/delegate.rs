@@ -1,5 +1,6 @@
 use clap::Args;
-use crate::functions::validate_positive;
+use crate::amount::{Amount, validate_amount};
+use crate::profiles::Exposure;
 use crate::profiles::ProfileCollection;
 use eyre::Result;
 
@@ -11,15 +12,17 @@ pub struct Command {
     /// The validator address or moniker
     validator: Option<String>,
 
-    /// The amount to delegate
-    #[arg( value_parser = validate_positive::<f64>)]
-    quantity: Option<f64>,
+    /// The amount to delegate (e.g. 1.5, 1500000unom, all, 50%)
+    #[arg( value_parser = validate_amount)]
+    quantity: Option<Amount>,
 }
 
 impl Command {
     pub fn run(&self) -> Result<()> {
-        ProfileCollection::new()?
+        let collection = ProfileCollection::new()?;
+        let mut exposure = Exposure::new(&collection);
+        collection
             .profile_by_name_or_address_or_home_or_default(Some(&self.profile))?
-            .nomic_delegate(self.validator.clone(), self.quantity, false)
+            .nomic_delegate(self.validator.clone(), self.quantity, false, Some(&mut exposure))
     }
 }
@@ -1,5 +1,6 @@
 
 use clap::Args;
+use crate::privkey::ExportArgs;
 use crate::profiles::ProfileCollection;
 use eyre::Result;
 
@@ -8,12 +9,17 @@ use eyre::Result;
 pub struct Command {
     #[arg()]
     profile: Option<String>,
+
+    #[command(flatten)]
+    export_args: ExportArgs,
 }
 
 impl Command {
     pub fn run(&self) -> Result<()> {
         let profile = ProfileCollection::new()?
             .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?;
-        Ok(println!("{}", profile.key()?.export()))
+        let key = profile.key()?;
+        self.export_args.confirm("private key", key.address()?)?;
+        Ok(println!("{}", key.export().as_str()))
     }
 }
@@ -1,6 +1,7 @@
-
-
 use clap::Args;
+use crate::privkey::mnemonic;
+use crate::privkey::HdPathArgs;
+use crate::privkey::PrivKey;
 use crate::profiles::ProfileCollection;
 use eyre::Result;
 use std::path::Path;
@@ -12,19 +13,51 @@ pub struct Command {
     #[arg(required = true)]
     profile: String,
 
-    /// Hex string or filename containing the key
-    #[arg(required_unless_present = "stdin")]
+    /// Hex string or filename containing the key, or the mnemonic's file with --mnemonic.
+    /// A key or mnemonic given here is kept in shell history and shown by `ps`, pipe it
+    /// with --stdin, or leave it out with --mnemonic to be prompted
+    #[arg(required_unless_present_any = ["stdin", "mnemonic"])]
     key_or_file: Option<String>,
 
     /// Flag to explicitly read from stdin if no other input is provided
     #[arg(long, short, action = clap::ArgAction::SetTrue)]
     stdin: bool,
+
+    /// Derive the key from a 24 word BIP39 mnemonic, read from stdin or prompted for if not given
+    #[arg(long, short)]
+    mnemonic: bool,
+
+    #[command(flatten)]
+    hd_path_args: HdPathArgs,
+
+    /// Keep the mnemonic next to the key so `key mnemonic export` can show it,
+    /// encrypted with the key's passphrase when the key is encrypted
+    #[arg(long, requires = "mnemonic")]
+    keep_mnemonic: bool,
 }
 
 impl Command {
     pub fn run(&self) -> Result<()> {
         let collection = ProfileCollection::new()?;
 
+        if self.mnemonic {
+            let phrase = mnemonic::read(self.key_or_file.as_deref())?;
+            let hd_path = self.hd_path_args.hd_path();
+            let key = PrivKey::from_mnemonic(&phrase, &self.hd_path_args.password()?, &hd_path)?;
+            collection.import(&self.profile, &key.export(), true)?;
+            println!("Profile '{}' imported from mnemonic at {}: {}", self.profile, hd_path, key.address()?);
+
+            if self.keep_mnemonic {
+                let profile = ProfileCollection::new()?.profile_by_name_or_address_or_home(&self.profile)?.clone();
+                let key_file = profile.key_file()?;
+                if !mnemonic::save(key_file, &phrase)? {
+                    eprintln!("Warning: The mnemonic is kept unencrypted in {:?}, `key encrypt` encrypts it with the key",
+                        mnemonic::file(key_file));
+                }
+            }
+            return Ok(());
+        }
+
         // Determine the input source based on `key_or_file` and `stdin`
         if let Some(ref input) = self.key_or_file {
             if Path::new(input).exists() {
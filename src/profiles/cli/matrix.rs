@@ -0,0 +1,35 @@
+use clap::Args;
+use crate::profiles::DelegationMatrix;
+use crate::profiles::ProfileCollection;
+use eyre::Result;
+
+#[derive(Debug, Args)]
+#[command(about = "Delegations of all profiles grouped by validator")]
+pub struct Command {
+    /// Only profiles with this tag
+    #[arg(short, long, visible_alias = "group")]
+    tag: Option<String>,
+
+    /// Output JSON instead of a table
+    #[arg(short, long)]
+    json: bool,
+}
+
+impl Command {
+    pub fn run(&self) -> Result<()> {
+        let mut collection = ProfileCollection::load()?;
+        if let Some(tag) = &self.tag {
+            collection.filter_tag(tag)?;
+        }
+        let matrix = DelegationMatrix::new(&collection)?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&matrix)?);
+        } else {
+            println!("\n{}\n", matrix.table());
+        }
+        for warning in matrix.warnings() {
+            eprintln!("{}", warning);
+        }
+        Ok(())
+    }
+}
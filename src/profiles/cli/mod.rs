@@ -1,14 +1,19 @@
 pub mod address;
 pub mod auto;
 pub mod balance;
+pub mod calc;
 pub mod claim;
+pub mod compound;
 pub mod config;
 pub mod delegate;
 pub mod delegations;
 pub mod export;
 pub mod import;
+pub mod matrix;
 pub mod nomic;
+pub mod portfolio;
 pub mod profiles;
 pub mod redelegate;
 pub mod send;
 pub mod stats;
+pub mod sweep;
@@ -0,0 +1,27 @@
+use clap::Args;
+use crate::profiles::Portfolio;
+use crate::profiles::PortfolioOutputFormat;
+use crate::profiles::ProfileCollection;
+use eyre::Result;
+
+#[derive(Debug, Args)]
+#[command(about = "Overview of all profiles with grand totals")]
+pub struct Command {
+    /// Only profiles with this tag
+    #[arg(short, long, visible_alias = "group")]
+    tag: Option<String>,
+
+    /// Specify the output format
+    #[arg(short, long)]
+    format: Option<PortfolioOutputFormat>,
+}
+
+impl Command {
+    pub fn run(&self) -> Result<()> {
+        let mut collection = ProfileCollection::new()?;
+        if let Some(tag) = &self.tag {
+            collection.filter_tag(tag)?;
+        }
+        Portfolio::new(&collection).print(self.format.clone())
+    }
+}
@@ -1,6 +1,6 @@
 // src/redelegate.rs
 use clap::Args;
-use crate::functions::validate_positive;
+use crate::amount::{Amount, validate_amount};
 use crate::profiles::ProfileCollection;
 use eyre::Result;
 
@@ -21,10 +21,10 @@ pub struct Command {
 
     /// The quantity to redelegate in nom
     #[arg(
-        help = "Quantity to Redelegate (NOM)", 
-        value_parser = validate_positive::<f64>,
+        help = "Quantity to Redelegate (e.g. 1.5, 1500000unom, all, 50%)",
+        value_parser = validate_amount,
     )]
-    quantity: f64,
+    quantity: Amount,
 }
 
 impl Command {
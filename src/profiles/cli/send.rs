@@ -1,5 +1,5 @@
 use clap::Args;
-use crate::functions::validate_positive;
+use crate::amount::{Amount, validate_amount};
 use crate::profiles::ProfileCollection;
 use eyre::Result;
 
@@ -13,17 +13,17 @@ pub struct Command {
     /// The validator to delegate to
     #[arg(
         // short, long,
-        help = "destination address"
+        help = "Destination address, address book label or profile"
     )]
     destination: Option<String>,
 
     /// The quantity to send
     #[arg(
         // short, long, 
-        help = "Quantity to send", 
-        value_parser = validate_positive::<f64>,
+        help = "Quantity to send (e.g. 1.5, 1500000unom, all, 50%)",
+        value_parser = validate_amount,
     )]
-    quantity: Option<f64>,
+    quantity: Option<Amount>,
 }
 
 impl Command {
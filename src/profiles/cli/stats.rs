@@ -1,5 +1,3 @@
-
-
 use clap::Args;
 use crate::profiles::ProfileCollection;
 use eyre::Result;
@@ -7,15 +5,31 @@ use eyre::Result;
 #[derive(Debug, Args)]
 #[command( about = "Display Statistics")]
 pub struct Command {
-    #[arg()]
+    #[arg(conflicts_with = "tag")]
     profile: Option<String>,
+
+    /// All profiles with this tag
+    #[arg(short, long, visible_alias = "group")]
+    tag: Option<String>,
 }
 
 impl Command {
     pub fn run(&self) -> Result<()> {
-        let profile = ProfileCollection::new()?
-            .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?;
-        println!("\n{}\n", profile.report());
+        let mut collection = ProfileCollection::new()?;
+        match &self.tag {
+            Some(tag) => {
+                collection.filter_tag(tag)?;
+                collection.sort_by_name();
+                for profile in collection.iter() {
+                    println!("\n{}\n", profile.report());
+                }
+            },
+            None => {
+                let profile = collection
+                    .profile_by_name_or_address_or_home_or_default(self.profile.as_deref())?;
+                println!("\n{}\n", profile.report());
+            },
+        }
         Ok(())
     }
 }
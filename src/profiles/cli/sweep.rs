@@ -0,0 +1,40 @@
+use clap::Args;
+use crate::profiles::ProfileCollection;
+use eyre::Result;
+
+#[derive(Debug, Args)]
+#[command(about = "Send everything above the minimum balance from profiles to one address")]
+pub struct Command {
+    /// Destination address, address book label or profile
+    #[arg(required = true)]
+    destination: String,
+
+    /// Profiles to sweep, all profiles if none are given
+    #[arg()]
+    profiles: Vec<String>,
+
+    /// Only profiles with this tag
+    #[arg(short, long, visible_alias = "group")]
+    tag: Option<String>,
+
+    /// Claim rewards before sending
+    #[arg(short, long)]
+    claim: bool,
+
+    /// Send without asking for confirmation
+    #[arg(short, long)]
+    yes: bool,
+}
+
+impl Command {
+    pub fn run(&self) -> Result<()> {
+        let mut collection = ProfileCollection::new()?;
+        if !self.profiles.is_empty() {
+            collection.select(&self.profiles)?;
+        }
+        if let Some(tag) = &self.tag {
+            collection.filter_tag(tag)?;
+        }
+        collection.sweep(&self.destination, self.claim, self.yes)
+    }
+}
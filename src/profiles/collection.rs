@@ -1,18 +1,30 @@
+use crate::addressbook::AddressBook;
+use crate::amount::Amount;
 use clap::ValueEnum;
+use crate::fuzzy;
 use crate::global::PROFILES_DIR;
 use crate::privkey::FromPath;
+use crate::profiles::Exposure;
 use crate::profiles::Profile;
 use crate::validators::ValidatorCollection;
 use eyre::{eyre, Result};
 use fmt::table::{Table, TableBuilder};
 use log::warn;
 use once_cell::sync::OnceCell;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
+use tabled::builder::Builder;
+use tabled::settings::{Alignment, Border, Color, Modify, Style};
+use tabled::settings::object::{Cell, Columns, Rows};
 
-use crate::functions::is_valid_nomic_address;
+use crate::functions::looks_like_nomic_address;
+use crate::functions::NumberDisplay;
+use crate::functions::prompt_user;
+use crate::functions::TableColumns;
+use crate::functions::validate_nomic_address;
 
 #[derive(Debug)]
 pub struct ProfileCollection {
@@ -140,13 +152,35 @@ impl ProfileCollection {
         self.profile_by_home(search_path.as_ref())
     }
 
+    /// Finds a profile by its exact name, address, or home. Near misses on names are
+    /// offered for confirmation on a terminal, otherwise fail suggesting them.
+    pub fn find_profile<S: AsRef<Path> + AsRef<str>>(&self, search: S) -> Result<&Profile> {
+        if let Ok(profile) = self.profile_by_name_or_address_or_home(&search) {
+            return Ok(profile);
+        }
+
+        let search: &str = search.as_ref();
+        let names: Vec<&str> = self.profiles.iter().map(|profile| profile.name()).collect();
+        let indexes = fuzzy::rank(&names, search);
+        if indexes.is_empty() {
+            return Err(eyre!(
+                "Profile {} not found.{}",
+                search, fuzzy::did_you_mean(&fuzzy::suggest(&names, search, 3)),
+            ));
+        }
+
+        let options: Vec<String> = indexes.iter().map(|&index| names[index].to_string()).collect();
+        let choice = fuzzy::confirm("profile", search, &options)?;
+        Ok(&self.profiles[indexes[choice]])
+    }
+
     /// Finds a profile by its name, address, or home.
     pub fn profile_by_name_or_address_or_home_or_default<S: AsRef<str> + AsRef<Path>>(
         &self,
         search: Option<S>
     ) -> Result<Profile> {
         if let Some(search) = search {
-            self.profile_by_name_or_address_or_home(search).cloned()
+            self.find_profile(search).cloned()
         } else {
             let home = home::home_dir().ok_or_else(|| eyre!("Home directory not found"))?;
             Profile::new(
@@ -207,17 +241,17 @@ impl ProfileCollection {
         )
     }
 
-    /// Validates a Nomic address or retrieves it from a managed profile.
+    /// Validates a Nomic address or retrieves it from the address book or a managed profile.
     ///
     /// This function first checks if the provided input is a valid Nomic address. If it is, 
-    /// the address is returned directly. If the input is not a valid address, it is treated 
-    /// as a profile name or a home directory path, and the function attempts to retrieve 
-    /// the corresponding address from the managed profiles. If no input is provided, it 
-    /// creates a new profile using the home directory and retrieves its address.
+    /// the address is returned directly. If the input is not a valid address, it is looked up
+    /// as an address book label, then treated as a profile name or a home directory path, and
+    /// the function attempts to retrieve the corresponding address from the managed profiles.
+    /// If no input is provided, it creates a new profile using the home directory and retrieves its address.
     ///
     /// # Parameters
     /// - `name_or_address_or_home`: An optional string slice that may contain a valid Nomic address, 
-    ///   a profile name, or a home directory path.
+    ///   an address book label, a profile name, or a home directory path.
     ///
     /// # Returns
     /// - `Ok(String)`: A result containing the validated Nomic address as a string if found or 
@@ -251,8 +285,17 @@ impl ProfileCollection {
     /// ```
     pub fn validate_address(&self, name_or_address_or_home: Option<&str>) -> Result<String, eyre::Error> {
         match name_or_address_or_home {
-            Some(search) if is_valid_nomic_address(search) => Ok(search.to_string()),
-            _ => self.address(name_or_address_or_home),
+            Some(search) => validate_nomic_address(search).or_else(|e| {
+                // Report a mistyped address as such rather than as an unknown profile
+                if looks_like_nomic_address(search) {
+                    Err(e)
+                } else if let Some(entry) = AddressBook::load()?.get(search) {
+                    Ok(entry.address.clone())
+                } else {
+                    self.address(Some(search))
+                }
+            }),
+            None => self.address(name_or_address_or_home),
         }
     }
 
@@ -303,20 +346,164 @@ impl ProfileCollection {
 //    pub fn delegations(&self, name_or_address_or_home: Option<&str>) -> Result<Delegations> {
 //        Ok(self.profile_by_name_or_address_or_home_or_default(name_or_address_or_home)?.delegations()?.clone())
 //    }
-    pub fn send(&self, source: Option<&str>, destination: Option<&str>, quantity: Option<f64>) -> Result<()> {
+    /// Warns when `address` is neither one of our profiles nor in the address book
+    fn warn_unknown_destination(&self, address: &str) -> Result<()> {
+        if self.profile_by_address(address).is_err() && !AddressBook::load()?.contains_address(address) {
+            eprintln!("Warning: {} is not in the address book", address);
+        }
+        Ok(())
+    }
+
+    pub fn send(&self, source: Option<&str>, destination: Option<&str>, quantity: Option<Amount>) -> Result<()> {
+        let address = self.validate_address(destination)?;
+        self.warn_unknown_destination(&address)?;
         self.profile_by_name_or_address_or_home_or_default(source)?
-            .nomic_send(self.validate_address(destination)?, quantity)
+            .nomic_send(address, quantity)
+    }
+
+    /// Keeps only the profiles tagged `tag` in their config
+    pub fn filter_tag(&mut self, tag: &str) -> Result<()> {
+        self.profiles.retain(|profile| profile.config().has_tag(tag));
+        if self.profiles.is_empty() {
+            return Err(eyre!("No profiles tagged '{}'", tag));
+        }
+        Ok(())
+    }
+
+    /// Keeps only the profiles matching `searches` (names, addresses or homes)
+    pub fn select(&mut self, searches: &[String]) -> Result<()> {
+        let mut addresses = Vec::new();
+        for search in searches {
+            addresses.push(self.profile_by_name_or_address_or_home(search.as_str())?.address().to_string());
+        }
+        self.profiles.retain(|profile| addresses.iter().any(|a| a == profile.address()));
+        Ok(())
+    }
+
+    fn sweep_table(&self, destination: &str, claim: bool) -> String {
+        let nom = |n: u64| NumberDisplay::new(n).scale(6).decimal_places(6).trim(false).format();
+
+        let mut rows: Vec<TableColumns> = Vec::new();
+        rows.push(TableColumns::new(vec![
+            "Profile", "Balance", "Rewards", "Minimum", "Sendable",
+        ]));
+
+        let mut total = 0;
+        for profile in self.profiles.iter().filter(|p| p.address() != destination) {
+            let sendable = profile.sendable(claim, true).unwrap_or(0);
+            total += sendable;
+            rows.push(TableColumns::new(vec![
+                profile.name(),
+                &nom(*profile.balance()),
+                &if claim { nom(*profile.total_liquid()) } else { "-".to_string() },
+                &nom(*profile.minimum_balance()),
+                &nom(sendable),
+            ]));
+        }
+        rows.push(TableColumns::new(vec![ "Total", "", "", "", &nom(total) ]));
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([
+                row.cell0.clone(), row.cell1.clone(), row.cell2.clone(),
+                row.cell3.clone(), row.cell4.clone(),
+            ]);
+        }
+
+        let last = rows.len() - 1;
+        let mut table = builder.build();
+        table.with(Style::blank())
+            .with(Modify::new(Columns::new(1..)).with(Alignment::right()))
+            .with(Modify::new(Rows::first()).with(Border::new().set_bottom('-')))
+            .with(Modify::new(Rows::single(last)).with(Border::new().set_top('-')))
+            .with(Modify::new(Cell::new(last, 4)).with(Color::FG_GREEN))
+            ;
+
+        table.to_string()
+    }
+
+    /// Sends everything above the minimum balance from each profile to `destination`,
+    /// optionally claiming rewards first. Shows a preview and asks for confirmation unless `yes`.
+    pub fn sweep(&mut self, destination: &str, claim: bool, yes: bool) -> Result<()> {
+        let destination = self.validate_address(Some(destination))?;
+        self.warn_unknown_destination(&destination)?;
+        self.sort_by_name();
+
+        println!("\nSweep to {}\n\n{}\n", destination, self.sweep_table(&destination, claim));
+
+        if !yes {
+            let user_input = prompt_user("Send these amounts?")?;
+            if !matches!(user_input.as_str(), "y" | "Y") {
+                return Err(eyre!("Sweep was not confirmed."));
+            }
+        }
+
+        let mut failed = Vec::new();
+        for profile in self.profiles.iter_mut().filter(|p| p.address() != destination) {
+            let quantity = profile.sendable(claim, true).unwrap_or(0);
+            if quantity == 0 {
+                continue;
+            }
+            if claim && *profile.total_liquid() > profile.claim_fee() {
+                if let Err(e) = profile.nomic_claim() {
+                    eprintln!("{}: failed to claim: {}", profile.name(), e);
+                    failed.push(profile.name().to_string());
+                    continue;
+                }
+            }
+            match profile.nomic_send(destination.clone(), Some(Amount::Unom(quantity))) {
+                Ok(()) => println!("{}: sent {}", profile.name(), Amount::Unom(quantity)),
+                Err(e) => {
+                    eprintln!("{}: failed to send: {}", profile.name(), e);
+                    failed.push(profile.name().to_string());
+                },
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(eyre!("Sweep failed for: {}", failed.join(", ")))
+        }
+    }
+
+    /// Validator addresses our profiles have stake with, mapped to the names of those profiles
+    pub fn delegated_validators(&self) -> HashMap<String, Vec<String>> {
+        let mut delegated: HashMap<String, Vec<String>> = HashMap::new();
+        for profile in self.profiles.iter() {
+            match profile.delegations() {
+                Ok(delegations) => {
+                    for (address, delegation) in delegations.delegations.iter() {
+                        if delegation.staked > 0 {
+                            delegated.entry(address.clone()).or_default().push(profile.name().to_string());
+                        }
+                    }
+                },
+                Err(e) => warn!("Could not load delegations for {}: {}", profile.name(), e),
+            }
+        }
+        delegated
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Profile> {
+        self.profiles.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Profile> {
+        self.profiles.iter_mut()
     }
 
     pub fn sort_by_name(&mut self) {
         self.profiles.sort_by(|a, b| a.name().cmp(&b.name()));
     }
 
-    pub fn auto_delegate(&mut self) -> Result<()> {
+    /// Delegates for every profile, keeping the fleet within the exposure cap.
+    /// `exposure` should cover all profiles, not only the ones being delegated.
+    pub fn auto_delegate(&mut self, exposure: &mut Exposure) -> Result<()> {
         self.sort_by_name();
         self.profiles.iter_mut().for_each(|profile| {
             // Call nomic_delegate and ignore any errors
-            let _ = profile.nomic_delegate(None, None, true);
+            let _ = profile.nomic_delegate(None, None, true, Some(&mut *exposure));
         });
         Ok(())
     }
@@ -342,14 +529,14 @@ impl ProfileCollection {
         name: &str, 
         file: &Path
     ) -> Result<()> {
-        self.import(name, file.privkey()?.export(), true)
+        self.import(name, &file.privkey()?.export(), true)
     }
 
     /// Retrieves validators, initializing it if necessary.
     /// blockchain operation, cache with oncecell
     pub fn validators(&self) -> eyre::Result<&ValidatorCollection> {
         self.validators.get_or_try_init(|| {
-            ValidatorCollection::fetch()
+            ValidatorCollection::cached()
         })
     }
 
@@ -372,6 +559,10 @@ impl ProfileCollection {
                     "nonce_file" : profile.nonce_file()?.to_string_lossy(),
                     "config_file": profile.config_file().to_string_lossy(),
                     "nonce"      : profile.export_nonce().ok(),
+                    "linked_addresses": profile.linked_addresses()?
+                        .into_iter()
+                        .map(|(prefix, address)| (prefix, serde_json::Value::String(address)))
+                        .collect::<serde_json::Map<String, serde_json::Value>>(),
                 }),
             );
         }
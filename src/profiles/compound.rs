@@ -0,0 +1,160 @@
+use crate::functions::format_duration;
+use crate::functions::NumberDisplay;
+use crate::functions::TableColumns;
+use tabled::builder::Builder;
+use tabled::settings::{Alignment, Color, Modify, Span, Style};
+use tabled::settings::object::{Cell, Columns};
+
+/// Integer square root, rounded down.
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method, starting from a guess that is always >= sqrt(n)
+    let mut x = n;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Compounding calculator for a single profile.
+///
+/// Every compounding cycle pays `claim_fee + stake_fee`. Compounding too often wastes
+/// rewards on fees, while compounding too rarely leaves rewards sitting idle instead of
+/// earning. For a threshold `T` (the amount restaked each cycle) the daily cost is:
+///
+/// - fee drag:          `fees * daily_reward / T` (cycles per day times fees per cycle)
+/// - idle reward drag:  `daily_reward * T / (2 * total_staked)` (on average `T / 2` is unstaked)
+///
+/// The sum is minimised at `T = sqrt(2 * fees * daily_reward / rate)` where
+/// `rate = daily_reward / total_staked`, which simplifies to `sqrt(2 * fees * total_staked)`.
+/// All amounts are in unom.
+#[derive(Clone, Debug)]
+pub struct Compound {
+    pub total_staked: u64,
+    pub daily_reward: u64,
+    pub claim_fee:    u64,
+    pub stake_fee:    u64,
+    pub rounding:     u64,
+}
+
+impl Compound {
+
+    pub fn new(
+        total_staked: u64,
+        daily_reward: u64,
+        claim_fee:    u64,
+        stake_fee:    u64,
+        rounding:     u64,
+    ) -> Self {
+        Self { total_staked, daily_reward, claim_fee, stake_fee, rounding }
+    }
+
+    /// Fees paid for one compounding cycle (claim + stake)
+    pub fn fees(&self) -> u64 {
+        self.claim_fee.saturating_add(self.stake_fee)
+    }
+
+    /// The fee-optimal threshold, before rounding.
+    /// Returns 0 if there is nothing staked or no reward to compound.
+    pub fn optimal_threshold(&self) -> u64 {
+        if self.total_staked == 0 || self.daily_reward == 0 {
+            return 0;
+        }
+        let product = 2u128
+            .saturating_mul(self.fees() as u128)
+            .saturating_mul(self.total_staked as u128);
+        isqrt(product).min(u64::MAX as u128) as u64
+    }
+
+    /// The fee-optimal threshold rounded to the nearest multiple of `rounding`,
+    /// never less than one `rounding` unit. Returns 0 if no optimum can be determined.
+    pub fn optimal_minimum_stake(&self) -> u64 {
+        let optimal = self.optimal_threshold();
+        if optimal == 0 || self.rounding == 0 {
+            return optimal;
+        }
+        let rounded = optimal.saturating_add(self.rounding / 2);
+        rounded
+            .saturating_sub(rounded % self.rounding)
+            .max(self.rounding)
+    }
+
+    /// Seconds needed to earn `threshold` at the current daily reward
+    pub fn cycle_seconds(&self, threshold: u64) -> u64 {
+        if self.daily_reward == 0 {
+            return 0;
+        }
+        threshold.saturating_mul(86_400).saturating_div(self.daily_reward)
+    }
+
+    /// Estimated daily cost (fees plus idle rewards) of compounding at `threshold`
+    pub fn daily_cost(&self, threshold: u64) -> u64 {
+        if threshold == 0 || self.total_staked == 0 {
+            return 0;
+        }
+        let daily = self.daily_reward as u128;
+        let fee_drag = (self.fees() as u128).saturating_mul(daily) / threshold as u128;
+        let idle_drag = daily.saturating_mul(threshold as u128) / (2 * self.total_staked as u128);
+        fee_drag.saturating_add(idle_drag).min(u64::MAX as u128) as u64
+    }
+
+    /// Estimated daily net yield when compounding at `threshold`
+    pub fn daily_net_yield(&self, threshold: u64) -> u64 {
+        self.daily_reward.saturating_sub(self.daily_cost(threshold))
+    }
+
+    /// Compares the `current` minimum stake against the fee-optimal one
+    pub fn report(&self, current: u64) -> String {
+        let optimal = self.optimal_minimum_stake();
+        let nom = |n: u64| NumberDisplay::new(n).scale(6).decimal_places(6).trim(true).format();
+
+        let rows = vec![
+            TableColumns::new(vec![ "Compounding:" ]),
+            TableColumns::new(vec![ "Total Staked:",  &nom(self.total_staked) ]),
+            TableColumns::new(vec![ "Daily Reward:",  &nom(self.daily_reward) ]),
+            TableColumns::new(vec![ "Fees per Cycle:", &nom(self.fees()) ]),
+            TableColumns::new(vec![ "", "Current", "Optimal" ]),
+            TableColumns::new(vec![
+                "Minimum Stake:",
+                &nom(current),
+                &nom(optimal),
+            ]),
+            TableColumns::new(vec![
+                "Cycle:",
+                &format_duration(self.cycle_seconds(current)),
+                &format_duration(self.cycle_seconds(optimal)),
+            ]),
+            TableColumns::new(vec![
+                "Daily Cost:",
+                &nom(self.daily_cost(current)),
+                &nom(self.daily_cost(optimal)),
+            ]),
+            TableColumns::new(vec![
+                "Daily Net Yield:",
+                &nom(self.daily_net_yield(current)),
+                &nom(self.daily_net_yield(optimal)),
+            ]),
+        ];
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([row.cell0.clone(), row.cell1.clone(), row.cell2.clone()]);
+        }
+
+        let mut table = builder.build();
+        table
+            .with(Style::empty())
+            .with(Modify::new(Columns::new(1..)).with(Alignment::right()))
+            .with(Modify::new(Columns::single(1)).with(Color::FG_BLUE))
+            .with(Modify::new(Columns::single(2)).with(Color::FG_GREEN))
+            .with(Modify::new(Cell::new(0, 0)).with(Span::column(3)))
+            .with(Modify::new(Cell::new(0, 0)).with(Color::new("\x1b[1m", "\x1b[0m")))
+            ;
+
+        table.to_string()
+    }
+}
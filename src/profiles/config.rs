@@ -1,4 +1,6 @@
+use crate::aliases::ValidatorAliases;
 use crate::functions::format_to_millions;
+use crate::functions::looks_like_nomic_address;
 use crate::global::CONFIG;
 use eyre::Result;
 use eyre::WrapErr;
@@ -30,14 +32,21 @@ pub fn config_filename() -> String {
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct ConfigValidator {
+    /// Validator address, or a global alias in the config file
     pub address: String,
     pub name: String,
+    /// The global alias `address` was resolved from, written back in its place on save
+    #[serde(skip)]
+    pub alias: Option<String>,
 }
 
 // Implement Display for ConfigValidator
 impl std::fmt::Display for ConfigValidator {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{} {}", self.address, self.name)
+        match &self.alias {
+            Some(alias) => write!(f, "{} {} (alias {})", self.address, self.name, alias),
+            None => write!(f, "{} {}", self.address, self.name),
+        }
     }
 }
 
@@ -46,8 +55,25 @@ impl ConfigValidator {
         Self { 
             address: address.to_string(), 
             name: name.to_string(),
+            alias: None,
         }
     }
+
+    /// A validator referenced by a global alias, `None` if the alias is unknown.
+    /// An empty `name` defaults to the alias.
+    pub fn from_alias(alias: &str, name: &str) -> Option<Self> {
+        let address = ValidatorAliases::shared().resolve(alias)?;
+        let alias = alias.trim().to_lowercase();
+        Some(Self {
+            address: address.to_string(),
+            name:    if name.is_empty() { alias.clone() } else { name.to_string() },
+            alias:   Some(alias),
+        })
+    }
+}
+
+fn default_optimize_minimum_stake() -> bool {
+    CONFIG.optimize_minimum_stake
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -57,8 +83,12 @@ pub struct Config {
     pub minimum_balance_ratio: u64,
     pub minimum_stake: u64,
     pub adjust_minimum_stake: bool,
+    #[serde(default = "default_optimize_minimum_stake")]
+    pub optimize_minimum_stake: bool,
     pub minimum_stake_rounding: u64,
     pub daily_reward: u64,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub validators: Vec<ConfigValidator>,
 }
 
@@ -71,8 +101,10 @@ impl std::fmt::Display for Config {
         writeln!(f, "{:22} : {}", "Minimum Balance Ratio", format_to_millions(self.minimum_balance_ratio, None))?;
         writeln!(f, "{:22} : {}", "Minimum Stake", format_to_millions(self.minimum_stake, None))?;
         writeln!(f, "{:22} : {}", "Adjust Minimum Stake", self.adjust_minimum_stake)?;
+        writeln!(f, "{:22} : {}", "Optimize Minimum Stake", self.optimize_minimum_stake)?;
         writeln!(f, "{:22} : {}", "Minimum Stake Rounding", format_to_millions(self.minimum_stake_rounding, None))?;
         writeln!(f, "{:22} : {}", "Daily Reward", format_to_millions(self.daily_reward, Some(2)))?;
+        writeln!(f, "{:22} : {}", "Tags", self.tags.join(", "))?;
 
         // Format the validators
         writeln!(f, "Validators:")?;
@@ -92,13 +124,33 @@ impl Default for Config {
             minimum_balance_ratio:  CONFIG.minimum_balance_ratio,  // Default to 0.001 (as f64 divided by 1_000_000.0)
             minimum_stake:          CONFIG.minimum_stake,          // Default minimum stake
             adjust_minimum_stake:   CONFIG.adjust_minimum_stake,   // Default adjustment to false
+            optimize_minimum_stake: CONFIG.optimize_minimum_stake, // Default optimization to false
             minimum_stake_rounding: CONFIG.minimum_stake_rounding, // Default rounding
             daily_reward:           0,                             // Default daily reward is zero
+            tags:                   Vec::new(),                    // No tags by default
             validators:             Vec::new(),                    // Start with no validators
         }
     }
 }
 
+/// Changes to a profile config, `None` and `false` leave a setting as it is
+#[derive(Clone, Debug, Default)]
+pub struct EditConfig {
+    pub minimum_balance:        Option<u64>,
+    pub minimum_balance_ratio:  Option<u64>,
+    pub minimum_stake:          Option<u64>,
+    pub adjust_minimum_stake:   Option<bool>,
+    pub optimize_minimum_stake: Option<bool>,
+    pub minimum_stake_rounding: Option<u64>,
+    pub daily_reward:           Option<u64>,
+    /// `address,name` or `alias[,name]`
+    pub add_validator:          Option<String>,
+    pub remove_validator:       Option<String>,
+    pub rotate_validators:      bool,
+    pub add_tag:                Option<String>,
+    pub remove_tag:             Option<String>,
+}
+
 impl Config {
 
     pub fn new(profile: &str,) -> Self {
@@ -112,6 +164,14 @@ impl Config {
         self.validators.push(validator);
     }
 
+    /// Adds a validator by global alias, kept as a reference to the alias
+    pub fn add_validator_alias(&mut self, alias: &str, name: &str) -> Result<()> {
+        let validator = ConfigValidator::from_alias(alias, name)
+            .ok_or_else(|| eyre::eyre!("Unknown validator alias '{}'", alias))?;
+        self.validators.push(validator);
+        Ok(())
+    }
+
     /// Save the current configuration to a TOML file.
     /// If `overwrite` is false and the file exists, it will return an error.
     pub fn save(&self, path: &Path, overwrite: bool) -> Result<()> {
@@ -122,7 +182,15 @@ impl Config {
             ));
         }
 
-        let toml_str = toml::to_string(self).wrap_err(
+        // Keep alias references so the config follows the alias when it is repointed
+        let mut stored = self.clone();
+        for validator in stored.validators.iter_mut() {
+            if let Some(alias) = validator.alias.take() {
+                validator.address = alias;
+            }
+        }
+
+        let toml_str = toml::to_string(&stored).wrap_err(
             "Failed to serialize config to TOML"
         )?;
 
@@ -146,10 +214,24 @@ impl Config {
             .wrap_err_with(|| format!("Failed to parse config file at {:?}", path))?;
 
         config.profile = profile.to_string();
+        config.resolve_aliases();
 
         Ok(config)
     }
 
+    /// Replaces alias references with the addresses they point to
+    fn resolve_aliases(&mut self) {
+        for validator in self.validators.iter_mut() {
+            if looks_like_nomic_address(&validator.address) {
+                continue;
+            }
+            match ConfigValidator::from_alias(&validator.address, &validator.name) {
+                Some(resolved) => *validator = resolved,
+                None => warn!("Profile {}: unknown validator alias '{}'", self.profile, validator.address),
+            }
+        }
+    }
+
     fn active_validator(&self) -> Result<&ConfigValidator> {
         self.validators.last()
             .ok_or_else(|| eyre::eyre!("No validators found"))
@@ -167,6 +249,9 @@ impl Config {
 
     pub fn validator_name(&self) -> &str {
         match self.active_validator() {
+            Ok(validator) if validator.name.is_empty() => {
+                ValidatorAliases::shared().alias(&validator.address).unwrap_or_default()
+            },
             Ok(validator) => &validator.name,
             Err(e) => {
                 warn!("No validators found: {}", e);
@@ -184,7 +269,8 @@ impl Config {
             .iter()
             .find(|validator| {
                 validator.address.to_lowercase() == search_lower ||
-                validator.name.to_lowercase() == search_lower
+                validator.name.to_lowercase() == search_lower ||
+                validator.alias.as_deref() == Some(search_lower.as_str())
             })
             .ok_or_else(|| eyre::eyre!("Validator not found"))
     }
@@ -197,11 +283,30 @@ impl Config {
         self.validators.retain(|validator| {
             validator.address.to_lowercase() != search_lower
                 && validator.name.to_lowercase() != search_lower
+                && validator.alias.as_deref() != Some(search_lower.as_str())
         });
 
         Ok(self)
     }
 
+    /// Tags are case-insensitive
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    pub fn add_tag(&mut self, tag: &str) -> &mut Self {
+        let tag = tag.trim();
+        if !tag.is_empty() && !self.has_tag(tag) {
+            self.tags.push(tag.to_string());
+        }
+        self
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) -> &mut Self {
+        self.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+        self
+    }
+
     pub fn rotate_validators(&mut self) -> &mut Self {
         // Rotate the validators if the list is not empty
         if let Some(last) = self.validators.pop() {
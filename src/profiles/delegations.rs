@@ -1,10 +1,14 @@
 
 use chrono::{Utc, DateTime, Local};
+use crate::cache;
 use crate::global::CONFIG;
+use crate::global::Runtime;
+use crate::privkey::PlaintextKey;
 use crate::validators::ValidatorCollection;
 use eyre::{eyre, Result};
 use indexmap::IndexMap;
 use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::path::Path;
 use std::process::Command;
@@ -14,7 +18,7 @@ use crate::functions::TableColumns;
 use crate::functions::NumberDisplay;
 use log::warn;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Delegation {
     pub staked: u64,
     pub liquid: u64,
@@ -27,12 +31,14 @@ impl Delegation {
         Delegation { staked, liquid, nbtc }
     }
 }
-#[derive(Clone)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Delegations {
     pub address:     String,
     pub timestamp:   DateTime<Utc>,
     pub delegations: IndexMap<String, Delegation>,
+    #[serde(skip)]
     pub total:       OnceCell<Delegation>,
+    #[serde(skip)]
     pub validators:  OnceCell<ValidatorCollection>,
 }
 
@@ -87,6 +93,19 @@ impl Delegations {
         })
     }
 
+    /// Fetches and caches the delegations, or in offline mode uses the last ones fetched
+    pub fn load<P: AsRef<Path>>(address: &str, home: Option<P>) -> Result<Self> {
+        let key = format!("delegations-{}", address);
+        if Runtime::offline() {
+            return cache::load::<Self>(&key)
+                .map(|cached| cached.value)
+                .ok_or_else(|| eyre!("No cached delegations for {}", address));
+        }
+        let delegations = Self::fetch(address, home)?;
+        cache::store(&key, &delegations);
+        Ok(delegations)
+    }
+
     /// Fetches the delegations from the command output and returns a new Delegations instance.
     pub fn fetch<P: AsRef<Path>, S: AsRef<str>>(address: S, home: Option<P>) -> Result<Self> {
 
@@ -102,11 +121,10 @@ impl Delegations {
 
         cmd.arg("delegations");
 
-        if let Some(home_path) = home {
-            if let Some(home_str) = home_path.as_ref().to_str() {
-                cmd.env("HOME", home_str);
-            }
-        }
+        // nomic reads the address from the key
+        let _plaintext = home
+            .map(|home_path| PlaintextKey::set_home(&mut cmd, home_path.as_ref()))
+            .transpose()?;
 
         // Execute the command and collect the output
         let output = cmd.output().map_err(|e| eyre!("Failed to execute command: {}", e))?;
@@ -179,7 +197,7 @@ impl Delegations {
 
     pub fn validators(&self) -> eyre::Result<&ValidatorCollection> {
         self.validators.get_or_try_init(|| {
-            ValidatorCollection::fetch()
+            ValidatorCollection::cached()
         })
     }
 
@@ -0,0 +1,174 @@
+use crate::functions::NumberDisplay;
+use crate::global::{CONFIG, ExposureAction, ExposureCap};
+use crate::profiles::ProfileCollection;
+use eyre::eyre;
+use eyre::Result;
+use log::warn;
+use std::collections::HashMap;
+
+/// What the whole fleet has staked with each validator, checked against
+/// `exposure_cap` in the global config before delegating.
+#[derive(Clone, Debug, Default)]
+pub struct Exposure {
+    staked: HashMap<String, u64>,
+    total:  u64,
+    cap:    ExposureCap,
+}
+
+impl Exposure {
+
+    /// Aggregates the delegations of every profile in `collection`.
+    /// Nothing is fetched when no cap is configured.
+    pub fn new(collection: &ProfileCollection) -> Self {
+        let mut exposure = Self {
+            cap: CONFIG.exposure_cap.clone(),
+            ..Self::default()
+        };
+        if !exposure.cap.is_enabled() {
+            return exposure;
+        }
+        for profile in collection.iter() {
+            match profile.delegations() {
+                Ok(delegations) => {
+                    for (address, delegation) in delegations.delegations.iter() {
+                        exposure.record(address, delegation.staked);
+                    }
+                },
+                Err(e) => warn!("Could not load delegations for {}: {}", profile.name(), e),
+            }
+        }
+        exposure
+    }
+
+    pub fn staked(&self, validator: &str) -> u64 {
+        self.staked.get(validator).copied().unwrap_or(0)
+    }
+
+    /// Validators the fleet has stake with
+    fn validators(&self) -> u64 {
+        self.staked.values().filter(|staked| **staked > 0).count() as u64
+    }
+
+    /// The percent cap only applies once the fleet is spread over enough validators
+    /// to meet it, with fewer (or nothing staked yet) no validator could take more stake.
+    fn percent_applies(&self) -> bool {
+        self.cap.percent > 0
+            && self.cap.percent < 1_000_000
+            && self.validators() >= 1_000_000u64.div_ceil(self.cap.percent)
+    }
+
+    /// The most that can still be delegated to `validator` without breaching the cap.
+    /// The percent cap is measured against the fleet total after the delegation.
+    pub fn headroom(&self, validator: &str) -> u64 {
+        let staked = self.staked(validator);
+        let mut headroom = u64::MAX;
+
+        if self.cap.absolute > 0 {
+            headroom = headroom.min(self.cap.absolute.saturating_sub(staked));
+        }
+        if self.percent_applies() {
+            // (staked + q) / (total + q) <= percent  =>  q <= (percent * total - staked) / (1 - percent)
+            let allowed = (self.cap.percent as u128 * self.total as u128)
+                .saturating_sub(1_000_000 * staked as u128)
+                / (1_000_000 - self.cap.percent) as u128;
+            headroom = headroom.min(allowed.min(u64::MAX as u128) as u64);
+        }
+        headroom
+    }
+
+    pub fn allows(&self, validator: &str, quantity: u64) -> bool {
+        !self.cap.is_enabled() || quantity <= self.headroom(validator)
+    }
+
+    /// Picks the validator to delegate `quantity` to: `preferred` if it stays within the cap,
+    /// otherwise, when the action is `divert`, the first of `candidates` that does.
+    pub fn resolve(&self, preferred: &str, quantity: u64, candidates: &[&str]) -> Result<String> {
+        if self.allows(preferred, quantity) {
+            return Ok(preferred.to_string());
+        }
+
+        let breach = eyre!(
+            "Delegating {} NOM to {} would exceed the exposure cap (headroom {} NOM)",
+            NumberDisplay::new(quantity).scale(6).decimal_places(6).trim(true).format(),
+            preferred,
+            NumberDisplay::new(self.headroom(preferred)).scale(6).decimal_places(6).trim(true).format(),
+        );
+
+        if self.cap.action == ExposureAction::Divert {
+            if let Some(candidate) = candidates.iter()
+                .find(|candidate| **candidate != preferred && self.allows(candidate, quantity))
+            {
+                warn!("{}, diverting to {}", breach, candidate);
+                return Ok(candidate.to_string());
+            }
+        }
+
+        Err(breach)
+    }
+
+    /// Adds a delegation to the aggregate
+    pub fn record(&mut self, validator: &str, quantity: u64) {
+        let staked = self.staked.entry(validator.to_string()).or_insert(0);
+        *staked = staked.saturating_add(quantity);
+        self.total = self.total.saturating_add(quantity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exposure(percent: u64, stakes: &[(&str, u64)]) -> Exposure {
+        let mut exposure = Exposure {
+            cap: ExposureCap { absolute: 0, percent, action: ExposureAction::Skip },
+            ..Exposure::default()
+        };
+        for (validator, quantity) in stakes {
+            exposure.record(validator, *quantity);
+        }
+        exposure
+    }
+
+    #[test]
+    fn empty_fleet_is_not_percent_capped() {
+        let exposure = exposure(250_000, &[]);
+        assert_eq!(exposure.headroom("a"), u64::MAX);
+        assert!(exposure.allows("a", 1_000_000));
+        assert_eq!(exposure.resolve("a", 1_000_000, &[]).unwrap(), "a");
+    }
+
+    #[test]
+    fn small_fleet_is_not_percent_capped() {
+        // 25% needs at least 4 validators
+        let exposure = exposure(250_000, &[("a", 100), ("b", 100), ("c", 100)]);
+        assert_eq!(exposure.headroom("a"), u64::MAX);
+        assert_eq!(exposure.headroom("d"), u64::MAX);
+    }
+
+    #[test]
+    fn percent_cap_applies_to_a_spread_fleet() {
+        let exposure = exposure(250_000, &[("a", 100), ("b", 100), ("c", 100), ("d", 100)]);
+        // (100 + q) / (400 + q) <= 0.25
+        assert_eq!(exposure.headroom("a"), 0);
+        // (0 + q) / (400 + q) <= 0.25
+        assert_eq!(exposure.headroom("e"), 133);
+        assert!(exposure.allows("e", 133));
+        assert!(!exposure.allows("e", 134));
+    }
+
+    #[test]
+    fn absolute_cap_applies_to_an_empty_fleet() {
+        let mut exposure = exposure(250_000, &[]);
+        exposure.cap.absolute = 1_000;
+        assert_eq!(exposure.headroom("a"), 1_000);
+    }
+
+    #[test]
+    fn divert_to_a_validator_with_headroom() {
+        let mut exposure = exposure(250_000, &[("a", 300), ("b", 100), ("c", 100), ("d", 100)]);
+        exposure.cap.action = ExposureAction::Divert;
+        assert_eq!(exposure.resolve("a", 50, &["a", "b"]).unwrap(), "b");
+        exposure.cap.action = ExposureAction::Skip;
+        assert!(exposure.resolve("a", 50, &["a", "b"]).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::functions::NumberDisplay;
+use crate::functions::TableColumns;
+use crate::global::CONFIG;
+use crate::profiles::ProfileCollection;
+use eyre::Result;
+use indexmap::IndexMap;
+use log::warn;
+use serde::Serialize;
+use tabled::builder::Builder;
+use tabled::settings::{Alignment, Border, Color, Modify, Style};
+use tabled::settings::object::{Columns, Rows};
+
+/// What our profiles have delegated to one validator, amounts in unom
+#[derive(Clone, Debug, Serialize)]
+pub struct MatrixRow {
+    pub address:      String,
+    pub moniker:      String,
+    pub rank:         Option<u64>,
+    pub voting_power: u64,
+    pub staked:       u64,
+    /// Names of our profiles delegating to this validator
+    pub profiles:     Vec<String>,
+    /// Our stake as a share of the validator's voting power, in ppm
+    pub share:        u64,
+}
+
+impl MatrixRow {
+    pub fn over_cap(&self, cap: u64) -> bool {
+        cap > 0 && self.share > cap
+    }
+}
+
+/// Every profile's delegations pivoted to one row per validator
+#[derive(Clone, Debug, Serialize)]
+pub struct DelegationMatrix {
+    pub rows: Vec<MatrixRow>,
+    /// Share cap in ppm from the global config, 0 when disabled
+    pub cap:  u64,
+}
+
+impl DelegationMatrix {
+
+    pub fn new(collection: &ProfileCollection) -> Result<Self> {
+        let validators = collection.validators()?;
+        let mut rows: IndexMap<String, MatrixRow> = IndexMap::new();
+
+        for profile in collection.iter() {
+            let delegations = match profile.delegations() {
+                Ok(delegations) => delegations,
+                Err(e) => {
+                    warn!("Could not load delegations for {}: {}", profile.name(), e);
+                    continue;
+                }
+            };
+            for (address, delegation) in delegations.delegations.iter() {
+                if delegation.staked == 0 {
+                    continue;
+                }
+                let row = rows.entry(address.clone()).or_insert_with(|| {
+                    let validator = validators.validator(address).ok();
+                    MatrixRow {
+                        address:      address.clone(),
+                        moniker:      validator.map(|v| v.moniker().to_string()).unwrap_or_else(|| "N/A".to_string()),
+                        rank:         validator.map(|v| v.rank()),
+                        voting_power: validator.map(|v| v.voting_power()).unwrap_or(0),
+                        staked:       0,
+                        profiles:     Vec::new(),
+                        share:        0,
+                    }
+                });
+                row.staked = row.staked.saturating_add(delegation.staked);
+                row.profiles.push(profile.name().to_string());
+            }
+        }
+
+        let mut rows: Vec<MatrixRow> = rows.into_values()
+            .map(|mut row| {
+                if row.voting_power > 0 {
+                    row.share = (row.staked as u128 * 1_000_000 / row.voting_power as u128) as u64;
+                }
+                row
+            })
+            .collect();
+        rows.sort_by(|a, b| b.staked.cmp(&a.staked));
+
+        Ok(Self { rows, cap: CONFIG.validator_share_cap })
+    }
+
+    pub fn total_staked(&self) -> u64 {
+        self.rows.iter().map(|row| row.staked).sum()
+    }
+
+    /// Validators where our combined share exceeds the cap
+    pub fn over_cap(&self) -> Vec<&MatrixRow> {
+        self.rows.iter().filter(|row| row.over_cap(self.cap)).collect()
+    }
+
+    pub fn table(&self) -> String {
+        let percent = |ppm: u64| format!("{}%", NumberDisplay::new(ppm).scale(4).decimal_places(2).format());
+
+        let mut rows: Vec<TableColumns> = Vec::new();
+        rows.push(TableColumns::new(vec![
+            "Rank", "Validator Address", "Moniker", "Voting Power", "Staked", "Profiles", "Share",
+        ]));
+        for row in &self.rows {
+            rows.push(TableColumns::new(vec![
+                &row.rank.map(|r| r.to_string()).unwrap_or_else(|| "N/A".to_string()),
+                &row.address,
+                &row.moniker,
+                &NumberDisplay::new(row.voting_power).scale(6).decimal_places(0).format(),
+                &NumberDisplay::new(row.staked).scale(6).decimal_places(2).format(),
+                &row.profiles.len().to_string(),
+                &percent(row.share),
+            ]));
+        }
+        rows.push(TableColumns::new(vec![
+            "", "", "", "",
+            &NumberDisplay::new(self.total_staked()).scale(6).decimal_places(2).format(),
+        ]));
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([
+                row.cell0.clone(), row.cell1.clone(), row.cell2.clone(), row.cell3.clone(),
+                row.cell4.clone(), row.cell5.clone(), row.cell6.clone(),
+            ]);
+        }
+
+        let last = rows.len() - 1;
+        let mut table = builder.build();
+        table.with(Style::blank())
+            .with(Modify::new(Columns::single(0)).with(Alignment::right()))
+            .with(Modify::new(Columns::new(3..)).with(Alignment::right()))
+            .with(Modify::new(Rows::first()).with(Border::new().set_bottom('-')))
+            .with(Modify::new(Rows::single(last)).with(Border::new().set_top('-')))
+            ;
+        for (index, row) in self.rows.iter().enumerate() {
+            if row.over_cap(self.cap) {
+                table.with(Modify::new(Rows::single(index + 1)).with(Color::FG_RED));
+            }
+        }
+
+        table.to_string()
+    }
+
+    /// One line per validator where our share exceeds the cap
+    pub fn warnings(&self) -> Vec<String> {
+        let percent = |ppm: u64| format!("{}%", NumberDisplay::new(ppm).scale(4).decimal_places(2).trim(true).format());
+        self.over_cap().iter()
+            .map(|row| format!(
+                "Warning: our share of {} ({}) is {}, above the cap of {}",
+                row.moniker, row.address, percent(row.share), percent(self.cap),
+            ))
+            .collect()
+    }
+}
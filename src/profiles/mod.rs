@@ -1,19 +1,36 @@
+mod assign;
 mod balance;
+pub mod calc;
 mod collection;
+mod compound;
 mod config;
 mod delegations;
+mod exposure;
+mod matrix;
+mod portfolio;
 mod profile;
 mod util;
 pub mod cli;
 
+pub use assign::AssignRules;
+pub use assign::ValidatorAssignment;
 pub use balance::Balance;
+pub use calc::Calc;
+pub use calc::CalcInput;
 pub use collection::OutputFormat as CollectionOutputFormat;
 pub use collection::ProfileCollection;
+pub use compound::Compound;
 pub use config::Config;
+pub use config::ConfigValidator;
+pub use config::EditConfig;
 
 pub use config::config_filename;
 pub use delegations::Delegations;
 pub use delegations::Delegation;
+pub use exposure::Exposure;
+pub use matrix::DelegationMatrix;
+pub use portfolio::OutputFormat as PortfolioOutputFormat;
+pub use portfolio::Portfolio;
 //pub use delegations::DelegationRow;
 pub use profile::Profile;
 pub use util::nomic;
@@ -0,0 +1,238 @@
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
+use crate::functions::format_duration;
+use crate::functions::NumberDisplay;
+use crate::functions::TableColumns;
+use crate::profiles::Profile;
+use crate::profiles::ProfileCollection;
+use eyre::Result;
+use serde::Serialize;
+use std::str::FromStr;
+use tabled::builder::Builder;
+use tabled::settings::{Alignment, Border, Color, Modify, Style};
+use tabled::settings::object::{Columns, Rows};
+
+/// One line of the portfolio, amounts in unom
+#[derive(Clone, Debug, Serialize)]
+pub struct PortfolioRow {
+    pub profile:           String,
+    pub address:           String,
+    pub balance:           u64,
+    pub total_staked:      u64,
+    pub total_liquid:      u64,
+    pub validator:         String,
+    pub validator_address: String,
+    pub minimum_stake:     u64,
+    /// Seconds until the next delegation, 0 if ready now, `None` if there is no reward to wait for
+    pub eta_seconds:       Option<u64>,
+    /// RFC 3339 timestamp of the last journal entry
+    pub last_action:       Option<String>,
+}
+
+impl PortfolioRow {
+
+    pub fn from_profile(profile: &Profile) -> Self {
+        let calc = profile.calc();
+        let daily_reward = profile.daily_reward();
+        let eta_seconds = if calc.remaining == 0 {
+            Some(0)
+        } else if daily_reward == 0 {
+            None
+        } else {
+            Some(calc.remaining.saturating_mul(86_400).saturating_div(daily_reward))
+        };
+        let last_action = profile.last_journal().ok()
+            .and_then(|journal| journal.get::<DateTime<Utc>>("timestamp"))
+            .map(|timestamp| timestamp.to_rfc3339());
+
+        Self {
+            profile:           profile.name().to_string(),
+            address:           profile.address().to_string(),
+            balance:           *profile.balance(),
+            total_staked:      *profile.total_staked(),
+            total_liquid:      *profile.total_liquid(),
+            validator:         profile.config().validator_name().to_string(),
+            validator_address: profile.config().validator_address().to_string(),
+            minimum_stake:     *profile.minimum_stake(),
+            eta_seconds,
+            last_action,
+        }
+    }
+
+    fn eta(&self) -> String {
+        match self.eta_seconds {
+            Some(0) => "ready".to_string(),
+            Some(seconds) => format_duration(seconds),
+            None => "-".to_string(),
+        }
+    }
+
+    fn last_action_local(&self) -> String {
+        self.last_action.as_deref()
+            .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+            .map(|timestamp| timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PortfolioTotals {
+    pub balance:      u64,
+    pub total_staked: u64,
+    pub total_liquid: u64,
+    pub total:        u64,
+}
+
+/// One row per profile plus grand totals
+#[derive(Clone, Debug, Serialize)]
+pub struct Portfolio {
+    pub rows:   Vec<PortfolioRow>,
+    pub totals: PortfolioTotals,
+}
+
+impl Portfolio {
+
+    pub fn new(collection: &ProfileCollection) -> Self {
+        let mut rows: Vec<PortfolioRow> = collection.iter()
+            .map(PortfolioRow::from_profile)
+            .collect();
+        rows.sort_by(|a, b| a.profile.cmp(&b.profile));
+
+        let mut totals = PortfolioTotals::default();
+        for row in &rows {
+            totals.balance      = totals.balance.saturating_add(row.balance);
+            totals.total_staked = totals.total_staked.saturating_add(row.total_staked);
+            totals.total_liquid = totals.total_liquid.saturating_add(row.total_liquid);
+        }
+        totals.total = totals.balance
+            .saturating_add(totals.total_staked)
+            .saturating_add(totals.total_liquid);
+
+        Self { rows, totals }
+    }
+
+    pub fn table(&self) -> String {
+        let nom = |n: u64| NumberDisplay::new(n).scale(6).decimal_places(2).format();
+
+        let mut rows: Vec<TableColumns> = Vec::new();
+        rows.push(TableColumns::new(vec![
+            "Profile", "Balance", "Staked", "Liquid", "Validator", "Min Stake", "Next", "Last Action",
+        ]));
+        for row in &self.rows {
+            rows.push(TableColumns::new(vec![
+                &row.profile,
+                &nom(row.balance),
+                &nom(row.total_staked),
+                &nom(row.total_liquid),
+                &row.validator,
+                &nom(row.minimum_stake),
+                &row.eta(),
+                &row.last_action_local(),
+            ]));
+        }
+        rows.push(TableColumns::new(vec![
+            "Total",
+            &nom(self.totals.balance),
+            &nom(self.totals.total_staked),
+            &nom(self.totals.total_liquid),
+            &format!("{} NOM", nom(self.totals.total)),
+        ]));
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([
+                row.cell0.clone(), row.cell1.clone(), row.cell2.clone(), row.cell3.clone(),
+                row.cell4.clone(), row.cell5.clone(), row.cell6.clone(), row.cell7.clone(),
+            ]);
+        }
+
+        let last = rows.len() - 1;
+        let mut table = builder.build();
+        table.with(Style::blank())
+            .with(Modify::new(Columns::new(1..4)).with(Alignment::right()))
+            .with(Modify::new(Columns::new(5..7)).with(Alignment::right()))
+            .with(Modify::new(Rows::first()).with(Border::new().set_bottom('-')))
+            .with(Modify::new(Rows::single(last)).with(Border::new().set_top('-')))
+            .with(Modify::new(Rows::single(last)).with(Color::FG_GREEN))
+            ;
+
+        table.to_string()
+    }
+
+    /// Amounts in unom, one line per profile followed by a totals line
+    pub fn csv(&self) -> String {
+        let mut output = String::from(
+            "profile,address,balance,total_staked,total_liquid,validator,validator_address,minimum_stake,eta_seconds,last_action\n"
+        );
+        let quote = |s: &str| if s.contains([',', '"', '\n']) {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        } else {
+            s.to_string()
+        };
+        for row in &self.rows {
+            output.push_str(&format!("{},{},{},{},{},{},{},{},{},{}\n",
+                quote(&row.profile),
+                row.address,
+                row.balance,
+                row.total_staked,
+                row.total_liquid,
+                quote(&row.validator),
+                row.validator_address,
+                row.minimum_stake,
+                row.eta_seconds.map(|s| s.to_string()).unwrap_or_default(),
+                row.last_action.as_deref().unwrap_or_default(),
+            ));
+        }
+        output.push_str(&format!("total,,{},{},{},,,,,\n",
+            self.totals.balance,
+            self.totals.total_staked,
+            self.totals.total_liquid,
+        ));
+        output
+    }
+
+    pub fn print(&self, format: Option<OutputFormat>) -> Result<()> {
+        match format {
+            Some(OutputFormat::Csv) => print!("{}", self.csv()),
+            Some(OutputFormat::Json) => println!("{}", serde_json::to_string(self)?),
+            Some(OutputFormat::JsonPretty) => println!("{}", serde_json::to_string_pretty(self)?),
+            Some(OutputFormat::Table) | None => println!("\n{}\n", self.table()),
+        }
+        Ok(())
+    }
+}
+
+/// Enum to represent output formats
+#[derive(Debug, Clone, ValueEnum)]
+pub enum OutputFormat {
+    Csv,
+    Json,
+    JsonPretty,
+    Table,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv"         => Ok(OutputFormat::Csv),
+            "json"        => Ok(OutputFormat::Json),
+            "json-pretty" => Ok(OutputFormat::JsonPretty),
+            "table"       => Ok(OutputFormat::Table),
+            _ => Err(format!("Invalid output format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            OutputFormat::Csv        => "csv",
+            OutputFormat::Json       => "json",
+            OutputFormat::JsonPretty => "json-pretty",
+            OutputFormat::Table      => "table",
+        };
+        write!(f, "{}", output)
+    }
+}
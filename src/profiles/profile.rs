@@ -1,22 +1,35 @@
 
 use chrono::{DateTime, Utc, Local};
+use crate::aliases::ValidatorAliases;
+use crate::amount::Amount;
 use crate::functions::format_date_offset;
 use crate::functions::format_duration;
-use crate::functions::is_valid_nomic_address;
+use crate::functions::looks_like_nomic_address;
 use crate::functions::NumberDisplay;
 use crate::functions::prompt_user;
 use crate::functions::TableColumns;
 use crate::functions::TaskStatus;
+use crate::functions::validate_nomic_address;
+use crate::functions::validate_validator_address;
 use crate::global::CONFIG;
 use crate::global::PROFILES_DIR;
+use crate::global::Runtime;
 use crate::journal::{Journal, OutputFormat};
 use crate::nonce::Nonce;
+use crate::privkey::PlaintextKey;
 use crate::privkey::PrivKey;
 use crate::profiles::Balance;
+use crate::profiles::calc;
+use crate::profiles::Calc;
+use crate::profiles::CalcInput;
+use crate::profiles::Compound;
 use crate::profiles::Config;
+use crate::profiles::ConfigValidator;
 use crate::profiles::config_filename;
 use crate::profiles::Delegation;
 use crate::profiles::Delegations;
+use crate::profiles::EditConfig;
+use crate::profiles::Exposure;
 use crate::profiles::ProfileCollection;
 use crate::validators::initialize_validators;
 use crate::validators::Validator;
@@ -27,10 +40,8 @@ use eyre::WrapErr;
 use log::warn;
 use once_cell::sync::OnceCell;
 use serde_json::Value;
-use std::cmp::max;
 use std::cmp::PartialEq;
 use std::env;
-use std::ffi::OsStr;
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
@@ -426,11 +437,26 @@ impl Profile {
             .unwrap_or("N/A")
     }
 
+    /// The same key's addresses on the chains in the global `address_prefixes`, nomic left out
+    pub fn linked_addresses(&self) -> Result<Vec<(String, String)>> {
+        let key = self.key()?;
+        Ok(CONFIG.address_prefixes.iter()
+            .filter(|prefix| prefix.as_str() != "nomic")
+            .filter_map(|prefix| match key.address_with_prefix(prefix) {
+                Ok(address) => Some((prefix.clone(), address)),
+                Err(e) => {
+                    warn!("Skipping address prefix {}: {}", prefix, e);
+                    None
+                },
+            })
+            .collect())
+    }
+
     /// Retrieves the balance, initializing it if necessary.
     /// blockchain operation, cache with oncecell
     pub fn balances(&self) -> eyre::Result<&Balance> {
         self.balances.get_or_try_init(|| {
-            Balance::fetch(Some(self.key()?.address()?))
+            Balance::load(self.key()?.address()?)
         })
     }
 
@@ -438,7 +464,7 @@ impl Profile {
     /// blockchain operation, cache with oncecell
     pub fn delegations(&self) -> Result<&Delegations> {
         self.delegations.get_or_try_init(|| {
-            Delegations::fetch(self.key()?.address()?, Some(self.home()))
+            Delegations::load(self.key()?.address()?, Some(self.home()))
         })
     }
 
@@ -446,7 +472,7 @@ impl Profile {
     /// blockchain operation, cache with oncecell
     pub fn validators(&self) -> eyre::Result<&ValidatorCollection> {
         self.validators.get_or_try_init(|| {
-            ValidatorCollection::fetch()
+            ValidatorCollection::cached()
         })
     }
 
@@ -464,71 +490,95 @@ impl Profile {
         })
     }
 
-    pub fn edit_config(
-        &self,
-        minimum_balance:        Option<u64>,
-        minimum_balance_ratio:  Option<u64>,
-        minimum_stake:          Option<u64>,
-        adjust_minimum_stake:   Option<bool>,
-        minimum_stake_rounding: Option<u64>,
-        daily_reward:           Option<u64>,
-        add_validator:          Option<String>,
-        remove_validator:       Option<String>,
-        rotate_validators:      bool,
-    ) -> Result<()> {
+    pub fn edit_config(&self, edit: EditConfig) -> Result<()> {
         let mut config = self.config().clone();
 
-        if let Some(balance) = minimum_balance {
+        if let Some(balance) = edit.minimum_balance {
             config.minimum_balance = balance;
         }
-        if let Some(balance_ratio) = minimum_balance_ratio {
+        if let Some(balance_ratio) = edit.minimum_balance_ratio {
             config.minimum_balance_ratio = balance_ratio;
         }
-        if let Some(stake) = minimum_stake {
+        if let Some(stake) = edit.minimum_stake {
             config.minimum_stake = stake;
         }
-        if let Some(adjust) = adjust_minimum_stake {
+        if let Some(adjust) = edit.adjust_minimum_stake {
             config.adjust_minimum_stake = adjust;
         }
-        if let Some(rounding) = minimum_stake_rounding {
+        if let Some(optimize) = edit.optimize_minimum_stake {
+            config.optimize_minimum_stake = optimize;
+        }
+        if let Some(rounding) = edit.minimum_stake_rounding {
             config.minimum_stake_rounding = rounding;
         }
-        if let Some(reward) = daily_reward {
+        if let Some(reward) = edit.daily_reward {
             config.daily_reward = reward;
         }
-        if let Some(address_and_name) = add_validator {
+        if let Some(address_and_name) = edit.add_validator {
             let parts: Vec<&str> = address_and_name.split(',').collect();
-            if parts.len() == 2 {
-                config.add_validator(parts[0], parts[1]);
+            if !looks_like_nomic_address(parts[0]) && parts.len() <= 2 {
+                config.add_validator_alias(parts[0], parts.get(1).copied().unwrap_or_default())?;
+            } else if parts.len() == 2 {
+                let address = validate_validator_address(parts[0])?;
+                config.add_validator(&address, parts[1]);
             } else {
-                warn!("Expected 'address,name' format, but got '{}'", address_and_name);
+                warn!("Expected 'address,name' or 'alias[,name]' format, but got '{}'", address_and_name);
             }
         }
-        if let Some(search) = remove_validator {
+        if let Some(search) = edit.remove_validator {
             config.remove_validator(&search)?;
         }
-        if rotate_validators {
+        if edit.rotate_validators {
             config.rotate_validators();
         }
+        if let Some(tag) = edit.add_tag {
+            config.add_tag(&tag);
+        }
+        if let Some(tag) = edit.remove_tag {
+            config.remove_tag(&tag);
+        }
 
         config.save(&self.config_file(), true)?;
         println!("{}", config);
         Ok(())
     }
 
-    pub fn set_config_minimum_balance(&self, minimum_balance: Option<u64>) -> Result<()> {
-        let balance = minimum_balance.unwrap_or_else(|| *self.minimum_balance());
-        self.edit_config( Some(balance), None, None, None, None, None, None, None, false)
+    /// Writes `validators` into the config, the last one becoming the active validator.
+    /// Validators already in the config are kept unless `replace`.
+    pub fn assign_validators(&self, validators: &[ConfigValidator], replace: bool) -> Result<()> {
+        let mut config = self.config().clone();
+        if replace {
+            config.validators.clear();
+        }
+        for validator in validators {
+            config.remove_validator(&validator.address)?;
+            config.add_validator(&validator.address, &validator.name);
+        }
+        config.save(&self.config_file(), true)
+    }
+
+    pub fn set_config_minimum_balance(&self, minimum_balance: Option<Amount>) -> Result<()> {
+        let balance = match minimum_balance {
+            Some(amount) => amount.unom()?,
+            None => *self.minimum_balance(),
+        };
+        self.edit_config(EditConfig { minimum_balance: Some(balance), ..Default::default() })
     }
 
-    pub fn set_config_minimum_stake(&self, minimum_stake: Option<u64>) -> Result<()> {
-        let stake = minimum_stake.unwrap_or_else(|| *self.minimum_stake());
-        self.edit_config( None, None, Some(stake), None, None, None, None, None, false)
+    pub fn set_config_minimum_stake(&self, minimum_stake: Option<Amount>) -> Result<()> {
+        let stake = match minimum_stake {
+            Some(amount) => amount.unom()?,
+            None => *self.minimum_stake(),
+        };
+        self.edit_config(EditConfig { minimum_stake: Some(stake), ..Default::default() })
     }
 
-    pub fn set_config_daily_reward(&self, daily_reward: Option<u64>) -> Result<()> {
-        let reward = daily_reward.unwrap_or_else(|| self.daily_reward());
-        self.edit_config( None, None, None, None, None, Some(reward), None, None, false)
+    pub fn set_config_daily_reward(&self, daily_reward: Option<Amount>) -> Result<()> {
+        let reward = match daily_reward {
+            Some(amount) => amount.unom()?,
+            None => self.daily_reward(),
+        };
+        self.edit_config(EditConfig { daily_reward: Some(reward), ..Default::default() })
     }
 
     /// import a new private key into profile
@@ -624,14 +674,15 @@ impl Profile {
             .iter()
             .find(|validator| validator.name == search || validator.address == search)
             .map(|validator| validator.name.as_str())
+            // Then the global alias for the address
+            .or_else(|| ValidatorAliases::shared().alias(search))
             // If no match, try searching `self.validators()` by `moniker` or `address`
             .or_else(|| {
                 // Handle `self.validators()` Result and search within `moniker` or `address`
                 self.validators().ok()
                     .and_then(|validators| {
-                        validators
-                            .iter()
-                            .find(|validator| validator.moniker() == search || validator.address() == search)
+                        validators.validator(search).ok()
+                            .or_else(|| validators.validator_by_moniker(search))
                             .map(|validator| validator.moniker())
                     })
             })
@@ -725,18 +776,29 @@ impl Profile {
             None => return Ok(self.config().validator_address().to_string()), // return early
         };
 
+        // A mistyped address would otherwise surface as "validator not found"
+        if looks_like_nomic_address(search) {
+            validate_validator_address(search)?;
+        }
+
+        // Global aliases take precedence over per-profile names and monikers
+        if let Some(address) = ValidatorAliases::shared().resolve(search) {
+            return Ok(address.to_string());
+        }
+
         let address = match self.config().search_validator(&search) {
             Ok(validator) => validator.address.clone(),
             Err(_) => {
-                // Search didn't match a config moniker, assume it's a profile, address, or home folder
-                match ProfileCollection::new()?.address(Some(search)) {
+                // Search didn't match a config moniker, assume it's a profile, address, or home folder.
+                // Only exact matches, near misses are confirmed against the validator set below.
+                match ProfileCollection::new()?.profile_by_name_or_address_or_home(search).map(|p| p.address().to_string()) {
                     Ok(a) => {
                         // If we get an address, confirm it's actually a validator
                         self.validators()?.validator(&a)?.address().to_string()
                     },
                     Err(_) => {
                         // Final fallback: assume it's either a validator moniker or address
-                        self.validators()?.validator(&search)?.address().to_string()
+                        self.validators()?.find_one(&search)?.address().to_string()
                     }
                 }
             },
@@ -769,27 +831,7 @@ impl Profile {
     ///
     pub fn minimum_balance(&self) -> &u64 {
         self.minimum_balance.get_or_init(|| {
-            // Load configuration to retrieve minimum balance ratio and base minimum balance.
-            let config = self.config();
-
-            // Adjusted ratio is the minimum of the `minimum_balance_ratio` and 1_000_000
-            // This effectively scales the ratio between 0 and 1 when divided by 1,000,000.
-            let adjusted_ratio = config.minimum_balance_ratio.min(1_000_000);
-
-            match self.delegations() {
-                Ok(d) => {
-                    // Calculate the minimum balance based on staked amount and adjusted ratio
-                    d.total()
-                        .staked
-                        .saturating_mul(adjusted_ratio)   // Apply ratio to staked amount
-                        .saturating_div(1_000_000)        // Scale down to the original ratio (0-1)
-                        .saturating_div(10_000)           // Round down to nearest 10_000 unom
-                        .saturating_mul(10_000)           // Ensure 10_000 unom increments
-                        .max(config.minimum_balance)      // Ensure result meets or exceeds config minimum
-                }
-                // If delegation information isn't available, default to config's minimum balance.
-                Err(_) => config.minimum_balance,
-            }
+            calc::minimum_balance(*self.total_staked(), self.config())
         })
     }
 
@@ -984,31 +1026,28 @@ impl Profile {
         }
     }
 
+    /// Compounding calculator for the current stake, reward and fees
+    pub fn compound(&self) -> Compound {
+        Compound::new(
+            *self.total_staked(),
+            self.daily_reward(),
+            self.claim_fee(),
+            self.stake_fee(),
+            self.config().minimum_stake_rounding,
+        )
+    }
+
+    /// Returns the minimum stake, the quantity that triggers a delegation.
+    /// See `calc::minimum_stake` for how it is derived from the config.
     pub fn minimum_stake(&self) -> &u64 {
         self.minimum_stake.get_or_init(|| {
-            let mut config = self.config().clone();
-            let config_min = config.minimum_stake;
-            let rounding   = config.minimum_stake_rounding;
-            let adjust     = config.adjust_minimum_stake;
-
-            let min = if adjust && rounding > 0 {
-                let daily = self.daily_reward().saturating_add(rounding.saturating_div(2));
-                max(config_min, daily.saturating_sub(daily % rounding))
-            } else {
-                config_min
-            };
-
-            if min != config_min {
-                config.minimum_balance = *self.minimum_balance();
-                config.minimum_stake = min;
-                config.daily_reward = self.daily_reward();
-                if let Err(_) = config.save(&self.config_file(), true) {
-                    warn!("Could not save config file");
-                }
-            }
-
-            // Return the calculated minimum stake
-            min
+            calc::minimum_stake(
+                *self.total_staked(),
+                self.daily_reward(),
+                self.claim_fee(),
+                self.stake_fee(),
+                self.config(),
+            )
         })
     }
 
@@ -1042,7 +1081,9 @@ impl Profile {
 //        })
 //    }
 
-    pub fn calc_quantity(&self, validator_address: Option<&str>, quantity: Option<u64>) -> Calc {
+    /// Gathers the inputs for the staking calculation from the chain and the config.
+    /// `validator_address` defaults to the active validator.
+    pub fn calc_input(&self, validator_address: Option<&str>, quantity: Option<u64>) -> CalcInput {
         let staked = match validator_address {
             Some(address) => {
                 self.delegations()
@@ -1052,65 +1093,23 @@ impl Profile {
             None => *self.validator_staked(),
         };
 
-        let available_without_claim = match self.balances() {
-            Ok(balances) => balances.nom
-                .saturating_sub(*self.minimum_balance())
-                .saturating_sub(self.stake_fee())
-                .max(0),
-            Err(_) => 0,
-        };
-
-        let available_after_claim = match self.balances() {
-            Ok(balances) => balances.nom
-                .saturating_add(self.delegations().map_or(0, |d| d.total().liquid))
-                .saturating_sub(*self.minimum_balance())
-                .saturating_sub(self.claim_fee())
-                .saturating_sub(self.stake_fee())
-                .max(0),
-            Err(_) => 0,
-        };
-
-        let remainder = staked % *self.minimum_stake();
-        let needed = quantity.unwrap_or_else(|| self.minimum_stake().saturating_sub(remainder));
-        let can_stake_without_claim = available_without_claim > needed;
-        let can_stake_after_claim = available_after_claim > needed;
-        let remaining = if can_stake_without_claim || can_stake_after_claim {
-            0
-        } else {
-            needed.saturating_sub(available_after_claim)
-        };
-        let needs_claim = !can_stake_without_claim && can_stake_after_claim;
-
-        // Calculate available funds based on conditions
-        let available = if can_stake_without_claim {
-            available_without_claim
-        } else if can_stake_after_claim {
-            available_after_claim
-        } else {
-            0
-        };
-
-        let quantity = quantity.unwrap_or_else(||
-            available
-                .saturating_sub(needed)
-                .saturating_div(*self.minimum_stake())
-                .saturating_mul(*self.minimum_stake())
-                .saturating_add(needed)
-        );
-
-        Calc {
-            available_without_claim,
-            available_after_claim,
-            remainder,
-            needed,
-            can_stake_without_claim,
-            can_stake_after_claim,
-            remaining,
-            needs_claim,
+        CalcInput {
+            balance:      *self.balance(),
+            liquid:       *self.total_liquid(),
+            total_staked: *self.total_staked(),
+            staked,
+            daily_reward: self.daily_reward(),
+            claim_fee:    self.claim_fee(),
+            stake_fee:    self.stake_fee(),
+            config:       self.config().clone(),
             quantity,
         }
     }
 
+    pub fn calc_quantity(&self, validator_address: Option<&str>, quantity: Option<u64>) -> Calc {
+        calc::calc(&self.calc_input(validator_address, quantity))
+    }
+
     pub fn calc(&self) -> &Calc {
         self.calc.get_or_init(|| {
             self.calc_quantity(None, None)
@@ -1145,20 +1144,6 @@ impl Profile {
 
 }
 
-#[derive(Clone, Debug)]
-pub struct Calc {
-    available_without_claim:  u64,
-    available_after_claim:    u64,
-    remainder:                u64,
-    needed:                   u64,
-    can_stake_without_claim:  bool,
-    can_stake_after_claim:    bool,
-    remaining:                u64,
-    needs_claim:              bool,
-    quantity:                 u64,
-
-}
-
 // Custom Display implementation for Profile
 impl std::fmt::Display for Profile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -1191,20 +1176,24 @@ impl PartialEq for Profile {
 
 impl Profile {
 
-    pub fn nomic_claim(&mut self) -> eyre::Result<()> {
-
-        // Create and configure the Command for running "nomic claim"
+    /// The nomic command with `NOMIC_LEGACY_VERSION` and `HOME` set, an encrypted key
+    /// stays decrypted until the `PlaintextKey` is dropped
+    fn nomic_command(&self) -> Result<(Command, PlaintextKey)> {
         let mut cmd = Command::new(CONFIG.nomic()?);
-        cmd.arg("claim");
-
-        // Set the environment variables for NOMIC_LEGACY_VERSION
         if let Some(ref version) = CONFIG.nomic_legacy_version {
             cmd.env("NOMIC_LEGACY_VERSION", version);
         }
+        let plaintext = PlaintextKey::set_home(&mut cmd, self.home())?;
+        Ok((cmd, plaintext))
+    }
+
+    pub fn nomic_claim(&mut self) -> eyre::Result<()> {
 
-        // Set the HOME environment variable
-        let home_path: &OsStr = self.home().as_os_str();
-        cmd.env("HOME", home_path);
+        Runtime::ensure_online("claim")?;
+
+        // Create and configure the Command for running "nomic claim"
+        let (mut cmd, _plaintext) = self.nomic_command()?;
+        cmd.arg("claim");
 
         // Execute the command and collect the output
         let output = cmd.output()?;
@@ -1226,10 +1215,13 @@ impl Profile {
     pub fn nomic_delegate(
         &mut self,
         validator: Option<String>,
-        quantity: Option<f64>,
+        quantity: Option<Amount>,
         log: bool,
+        exposure: Option<&mut Exposure>,
     ) -> eyre::Result<()> {
 
+        Runtime::ensure_online("delegate")?;
+
         let validator_address = match self.validator_address(validator.as_deref()) {
             Ok(address) => address,
             Err(e) => {
@@ -1238,7 +1230,8 @@ impl Profile {
             }
         };
 
-        let quantity_u64 = quantity.map(|n| (n * 1_000_000.0) as u64);
+        let input = self.calc_input(validator.as_deref(), None);
+        let quantity_u64 = quantity.map(|amount| calc::resolve_quantity(&input, amount));
 
         let calc = self.calc_quantity(validator.as_deref(), quantity_u64);
 
@@ -1253,6 +1246,24 @@ impl Profile {
             return Err(eyre!("Not enough balance to stake that quantity."));
         }
 
+        // Keep the fleet within the exposure cap, possibly diverting to another configured validator
+        let validator_address = match exposure.as_deref() {
+            Some(exposure) => {
+                let candidates: Vec<&str> = self.config().validators.iter()
+                    .rev()
+                    .map(|v| v.address.as_str())
+                    .collect();
+                match exposure.resolve(&validator_address, calc.quantity, &candidates) {
+                    Ok(address) => address,
+                    Err(e) => {
+                        if log { self.journal().print(Some(OutputFormat::Json))? };
+                        return Err(e);
+                    }
+                }
+            },
+            None => validator_address,
+        };
+
         if calc.needs_claim {
             if let Err(e) = self.nomic_claim() {
                 if log { self.journal().print(Some(OutputFormat::Json))? };
@@ -1268,16 +1279,7 @@ impl Profile {
         }
 
         // Create and configure the Command for running "nomic delegate"
-        let mut cmd = Command::new(CONFIG.nomic()?);
-
-        // Set the environment variables for NOMIC_LEGACY_VERSION
-        if let Some(ref version) = CONFIG.nomic_legacy_version {
-            cmd.env("NOMIC_LEGACY_VERSION", version);
-        }
-
-        // Assuming `self.home()` returns a &Path
-        let home_path: &OsStr = self.home().as_os_str();
-        cmd.env("HOME", home_path);
+        let (mut cmd, _plaintext) = self.nomic_command()?;
 
         // Add the "delegate" argument, validator, and quantity
         cmd.arg("delegate");
@@ -1298,6 +1300,9 @@ impl Profile {
             return Err(eyre!(error_msg));
         }
         self.staked = true;
+        if let Some(exposure) = exposure {
+            exposure.record(&validator_address, calc.quantity);
+        }
 //        let balance = self.balance()
 //            .saturating_sub(calc.quantity)
 //            .saturating_sub(self.stake_fee());
@@ -1331,25 +1336,30 @@ impl Profile {
         &self,
         source: &str,
         destination: &str,
-        quantity: f64,
+        quantity: Amount,
     ) -> eyre::Result<()> {
 
+        Runtime::ensure_online("redelegate")?;
+
         let source_address = self.validator_address(Some(source))?;
         let destination_address = self.validator_address(Some(destination))?;
 
-        let quantity = (quantity * 1_000_000.0) as u64;
+        // `all` and `50%` refer to what is staked with the source validator
+        let staked = self.delegations()?
+            .find(&source_address)
+            .map(|d| d.staked)
+            .unwrap_or(0);
+        let quantity = quantity.resolve(staked);
 
-        // Create and configure the Command for running "nomic delegate"
-        let mut cmd = Command::new(CONFIG.nomic()?);
-
-        // Set the environment variables for NOMIC_LEGACY_VERSION
-        if let Some(ref version) = CONFIG.nomic_legacy_version {
-            cmd.env("NOMIC_LEGACY_VERSION", version);
+        if quantity == 0 {
+            return Err(eyre!("Nothing to redelegate from {}", source_address));
+        }
+        if quantity > staked {
+            return Err(eyre!("Not enough staked with {} to redelegate", source_address));
         }
 
-        // Assuming `self.home()` returns a &Path
-        let home_path: &OsStr = self.home().as_os_str();
-        cmd.env("HOME", home_path);
+        // Create and configure the Command for running "nomic delegate"
+        let (mut cmd, _plaintext) = self.nomic_command()?;
 
         // Add the "delegate" argument, validator, and quantity
         cmd.arg("redelegate");
@@ -1374,39 +1384,47 @@ impl Profile {
 
     }
 
+    /// Fee held back when sending
+    pub fn send_fee(&self) -> u64 {
+        self.stake_fee().saturating_mul(10)
+    }
+
+    /// The amount that can be sent: the wallet balance, plus unclaimed rewards less
+    /// the claim fee if `claim` (or rewards were already claimed), less the send fee.
+    /// If `keep_minimum_balance`, the minimum balance stays in the wallet.
+    pub fn sendable(&self, claim: bool, keep_minimum_balance: bool) -> eyre::Result<u64> {
+        let mut available = self.balances()?.nom;
+        if claim || self.claimed {
+            available = available
+                .saturating_add(self.delegations()?.total().liquid)
+                .saturating_sub(self.claim_fee());
+        }
+        if keep_minimum_balance {
+            available = available.saturating_sub(*self.minimum_balance());
+        }
+        Ok(available.saturating_sub(self.send_fee()))
+    }
+
     pub fn nomic_send(
         &self,
         destination_address: String,
-        quantity: Option<f64>,
+        quantity: Option<Amount>,
     ) -> eyre::Result<()> {
 
-        if !is_valid_nomic_address(&destination_address) {
-            return Err(eyre!("Invalid address: {}", &destination_address));
-        }
+        Runtime::ensure_online("send")?;
 
-        let available = self.balances()?.nom
-            .saturating_sub(self.stake_fee().saturating_mul(10));
+        let destination_address = validate_nomic_address(&destination_address)?;
 
-        let quantity = match quantity {
-            Some(q) => (q * 1_000_000.0) as u64,
-            None => available,
-        };
+        let available = self.sendable(false, false)?;
+
+        let quantity = quantity.unwrap_or(Amount::All).resolve(available);
 
         if quantity > available {
             return Err(eyre!("Not enough to send"));
         }
 
         // Create and configure the Command for running "nomic delegate"
-        let mut cmd = Command::new(CONFIG.nomic()?);
-
-        // Set the environment variables for NOMIC_LEGACY_VERSION
-        if let Some(ref version) = CONFIG.nomic_legacy_version {
-            cmd.env("NOMIC_LEGACY_VERSION", version);
-        }
-
-        // Assuming `self.home()` returns a &Path
-        let home_path: &OsStr = self.home().as_os_str();
-        cmd.env("HOME", home_path);
+        let (mut cmd, _plaintext) = self.nomic_command()?;
 
         // Add the "delegate" argument, validator, and quantity
         cmd.arg("send");
@@ -1478,6 +1496,10 @@ impl Profile {
                 "config_adjust_minimum_stake".to_string(),
                 Value::Bool(self.config().adjust_minimum_stake)
             );
+            journal.insert(
+                "config_optimize_minimum_stake".to_string(),
+                Value::Bool(self.config().optimize_minimum_stake)
+            );
             journal.insert(
                 "config_minimum_stake_rounding".to_string(),
                 Value::Number(self.config().minimum_stake_rounding.into())
@@ -1486,6 +1508,10 @@ impl Profile {
                 "config_daily_reward".to_string(),
                 Value::Number(self.config().daily_reward.into())
             );
+            journal.insert(
+                "config_tags".to_string(),
+                Value::String(self.config().tags.join(","))
+            );
             journal.insert(
                 "config_validator_address".to_string(),
                 Value::String(self.config().validator_address().to_string())
@@ -1626,6 +1652,12 @@ impl Profile {
                 "Minimum Stake Rounding:",
                 &NumberDisplay::new(config.minimum_stake_rounding).scale(6).decimal_places(6).trim(true).format(),
             ]),
+            TableColumns::new(vec![
+                "Optimize Minimum Stake:",
+                &config.optimize_minimum_stake.to_string(),
+                "Tags:",
+                &config.tags.join(", "),
+            ]),
         ];
 
         // Initialize Builder without headers
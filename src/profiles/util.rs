@@ -1,5 +1,7 @@
 use clap::ValueEnum;
 use crate::global::CONFIG;
+use crate::global::Runtime;
+use crate::privkey::PlaintextKey;
 use eyre::{eyre, Result};
 use std::io::{BufReader, Read};
 use std::path::Path;
@@ -11,21 +13,22 @@ pub fn nomic(
     legacy: Option<String>,
     args: Vec<String>
 ) -> Result<(), eyre::Error> {
+    Runtime::ensure_online("run nomic")?;
+
     // Create the command based on whether legacy is provided or not
-    let mut child = if let Some(legacy_version) = legacy {
-        Command::new(CONFIG.nomic()?)
-            .env("NOMIC_LEGACY_VERSION", legacy_version)
-            .env("HOME", home.as_os_str())
-            .args(&args)
-            .stderr(Stdio::piped())  // Capture stderr for error handling
-            .spawn()?
+    let mut cmd = if let Some(legacy_version) = legacy {
+        let mut cmd = Command::new(CONFIG.nomic()?);
+        cmd.env("NOMIC_LEGACY_VERSION", legacy_version);
+        cmd
     } else {
         Command::new("nomic")
-            .env("HOME", home.as_os_str())
-            .args(&args)
-            .stderr(Stdio::piped())  // Capture stderr for error handling
-            .spawn()?
     };
+    // Held until nomic exits
+    let _plaintext = PlaintextKey::set_home(&mut cmd, home)?;
+    let mut child = cmd
+        .args(&args)
+        .stderr(Stdio::piped())  // Capture stderr for error handling
+        .spawn()?;
 
     // Capture stderr in a buffer
     let mut stderr = String::new();
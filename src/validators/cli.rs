@@ -1,6 +1,10 @@
 use clap::{Parser, Subcommand};
-use crate::validators::{OutputFormat, ValidatorCollection};
+use crate::profiles::{AssignRules, ProfileCollection, ValidatorAssignment};
+use crate::validators::{DiffOutputFormat, OutputFormat, Snapshot, StatsOutputFormat};
+use crate::validators::{ValidatorCollection, ValidatorDiff, ValidatorStats, ValidatorStatus};
+use crate::validators::validate_commission;
 use eyre::Result;
+use std::collections::HashSet;
 
 #[derive(Debug, Parser)]
 pub struct Options {
@@ -54,6 +58,14 @@ pub struct Cli {
     #[command(flatten)]
     pub options: Options,
 
+    /// Only validators with these statuses, e.g. active,jailed
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub status: Vec<ValidatorStatus>,
+
+    /// Only validators with at most this commission, e.g. 5% or 0.05
+    #[arg(long, global = true, value_parser = validate_commission)]
+    pub max_commission: Option<u64>,
+
     /// Subcommands for the validators command
     #[command(subcommand)]
     pub command: Option<CliCommand>,
@@ -97,6 +109,69 @@ pub enum CliCommand {
         #[command(flatten)]
         options: Options,
     },
+    /// Pick validators by rules and write them into profile configs
+    Assign {
+        /// Profiles to assign validators to
+        #[arg(required_unless_present = "tag")]
+        profiles: Vec<String>,
+
+        /// Assign to every profile with this tag
+        #[arg(long, visible_alias = "group", conflicts_with = "profiles")]
+        tag: Option<String>,
+
+        /// Number of validators to assign to each profile
+        #[arg(short = 'c', long, default_value_t = 1)]
+        count: usize,
+
+        /// Skip the validators that together hold this percentage of voting power
+        #[arg(short = 'p', long, default_value_t = 33.4)]
+        skip_top_percent: f64,
+
+        /// Lowest rank number allowed, e.g. 20 skips the top 19
+        #[arg(short = 'r', long, default_value_t = 1)]
+        min_rank: u64,
+
+        /// Allow validators already configured in other profiles
+        #[arg(short = 'a', long)]
+        allow_shared: bool,
+
+        /// Replace the validators in the config instead of adding to them
+        #[arg(long)]
+        replace: bool,
+
+        /// Show the assignment without writing any config
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+    /// Decentralization metrics and where our validators sit
+    Stats {
+        /// Report the cumulative share of the top N validators, can be repeated
+        #[arg(short = 'n', long = "top", default_values_t = [5, 10, 20])]
+        top: Vec<usize>,
+
+        /// Specify the output format
+        #[arg(long, short, default_value = "table")]
+        format: Option<StatsOutputFormat>,
+    },
+    /// Show what changed in the validator set between two snapshots
+    Diff {
+        /// Older snapshot: latest, ~N (N before the latest) or a date/time
+        from: String,
+
+        /// Newer snapshot, or `now` for the live set
+        #[arg(default_value = "now")]
+        to: String,
+
+        /// Only validators our profiles are configured with or delegate to
+        #[arg(short = 'o', long)]
+        ours: bool,
+
+        /// Specify the output format
+        #[arg(long, short, default_value = "table")]
+        format: Option<DiffOutputFormat>,
+    },
+    /// List saved validator set snapshots
+    Snapshots,
     /// Search for validators by moniker
     Moniker {
         /// Search for validators by moniker
@@ -139,9 +214,20 @@ pub enum CliCommand {
 }
 
 impl Cli {
+    /// Applies the --status and --max-commission filters
+    fn filter(&self, mut collection: ValidatorCollection) -> ValidatorCollection {
+        if !self.status.is_empty() {
+            collection = collection.filter_status(&self.status);
+        }
+        if let Some(max) = self.max_commission {
+            collection = collection.filter_commission(max);
+        }
+        collection
+    }
+
     // Change the function to be a method of Cli
     pub fn run(&self) -> Result<()> {
-        let collection = ValidatorCollection::fetch()?;
+        let collection = self.filter(ValidatorCollection::cached()?);
 
         // Handle subcommands
         match &self.command {
@@ -279,6 +365,71 @@ impl Cli {
                 }
             },
 
+            // Handle assign subcommand
+            Some(CliCommand::Assign {
+                profiles, tag, count, skip_top_percent, min_rank, allow_shared, replace, dry_run,
+            }) => {
+                // Validators used by the rest of the fleet still count, so plan against every profile
+                let fleet = ProfileCollection::new()?;
+                let mut targets = ProfileCollection::new()?;
+                match tag {
+                    Some(tag) => targets.filter_tag(tag)?,
+                    None => targets.select(profiles)?,
+                }
+                let targets: Vec<String> = targets.iter().map(|p| p.address().to_string()).collect();
+
+                let rules = AssignRules {
+                    count:            *count,
+                    skip_top_percent: *skip_top_percent,
+                    min_rank:         *min_rank,
+                    allow_shared:     *allow_shared,
+                    replace:          *replace,
+                };
+                let assignment = ValidatorAssignment::plan(&fleet, &collection, &targets, rules)?;
+                println!("\n{}\n", assignment.table());
+                if !dry_run {
+                    assignment.apply(&fleet)?;
+                }
+            },
+
+            // Handle stats subcommand
+            Some(CliCommand::Stats { top, format }) => {
+                let delegated = ProfileCollection::new()?.delegated_validators();
+                ValidatorStats::new(&collection, top, &delegated).print(format.clone())?;
+            },
+
+            // Handle diff subcommand
+            Some(CliCommand::Diff { from, to, ours, format }) => {
+                let load = |selector: &str| -> Result<ValidatorCollection> {
+                    if selector.eq_ignore_ascii_case("now") {
+                        Ok(collection.clone())
+                    } else {
+                        Ok(self.filter(Snapshot::find(selector)?.load()?))
+                    }
+                };
+                let mut diff = ValidatorDiff::new(&load(from)?, &load(to)?);
+                if *ours {
+                    let profiles = ProfileCollection::new()?;
+                    let mut addresses: HashSet<String> = profiles.delegated_validators().into_keys().collect();
+                    for profile in profiles.iter() {
+                        addresses.extend(profile.config().validators.iter().map(|v| v.address.clone()));
+                    }
+                    diff.retain(&addresses);
+                }
+                diff.print(format.clone())?;
+            },
+
+            // Handle snapshots subcommand
+            Some(CliCommand::Snapshots) => {
+                let snapshots = Snapshot::list()?;
+                if snapshots.is_empty() {
+                    eprintln!("No validator snapshots in {:?}", Snapshot::dir());
+                }
+                for (index, snapshot) in snapshots.iter().rev().enumerate() {
+                    println!("~{:<4} {}", index, snapshot.timestamp.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"));
+                }
+            },
+
             // Handle random subcommand
             Some(CliCommand::Random { count, skip_top, skip_bottom, options}) => {
                 let filtered = collection.random(Some(*count), Some(*skip_top), Some(*skip_bottom))?;
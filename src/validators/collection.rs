@@ -1,7 +1,11 @@
 use chrono::{Utc, DateTime};
 use clap::ValueEnum;
+use crate::cache;
+use crate::fuzzy;
 use crate::global::CONFIG;
-use crate::validators::validator::{Validator, ValidatorTableDetail, ValidatorTableSimple};
+use crate::global::Runtime;
+use crate::validators::snapshot;
+use crate::validators::validator::{Validator, ValidatorStatus, ValidatorTableDetail, ValidatorTableSimple};
 use eyre::eyre;
 use eyre::Result;
 use indexmap::IndexMap;
@@ -11,13 +15,14 @@ use rand::seq::SliceRandom;
 use serde_json;
 use serde_json::Value;
 use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::fs;
 use std::iter::FromIterator;
 use std::path::Path;
 use std::process::Command;
 use std::str::FromStr;
 use tabled::builder::Builder;
-use tabled::{Table, settings::{Alignment, Border, Modify, Style, object::{Columns, Cell}}};
+use tabled::{Table, settings::{Alignment, Border, Modify, Style, object::{Columns, Cell, Rows}}};
 use crate::functions::TableColumns;
 use crate::functions::NumberDisplay;
 use crate::functions::truncate_with_ellipsis;
@@ -71,15 +76,133 @@ impl std::fmt::Display for OutputFormat {
 
 // pub struct ValidatorCollection(Vec<Validator>);
 
-#[derive(Debug)] 
+/// Fields collected for one validator while parsing `nomic validators`
+#[derive(Default)]
+struct ParsedValidator {
+    address:      String,
+    voting_power: u64,
+    moniker:      String,
+    details:      String,
+    status:       ValidatorStatus,
+    jailed:       bool,
+    commission:   Option<u64>,
+    in_details:   bool,
+}
+
+impl ParsedValidator {
+    fn new(address: &str) -> Self {
+        Self { address: address.to_string(), ..Self::default() }
+    }
+
+    /// Sets the field for `label`, unknown labels are ignored but still end the details
+    fn set(&mut self, label: &str, value: &str) {
+        let label = label.trim().to_uppercase();
+        self.in_details = label == "DETAILS";
+        match label.as_str() {
+            "VOTING POWER" => self.voting_power = value.parse::<u64>().unwrap_or(0),
+            "MONIKER"      => self.moniker = value.to_string(),
+            "DETAILS"      => self.details = value.to_string(),
+            "STATUS"       => self.status = ValidatorStatus::parse(value),
+            "JAILED"       => self.jailed = matches!(value.to_lowercase().as_str(), "true" | "yes"),
+            "ACTIVE" | "IN ACTIVE SET" => if self.status == ValidatorStatus::Unknown {
+                self.status = match value.to_lowercase().as_str() {
+                    "true" | "yes" => ValidatorStatus::Active,
+                    _ => ValidatorStatus::Inactive,
+                };
+            },
+            "COMMISSION" | "COMMISSION RATE" => self.commission = parse_commission(value),
+            _ => {},
+        }
+    }
+
+    fn into_validator(self, rank: u64) -> Validator {
+        let status = if self.jailed { ValidatorStatus::Jailed } else { self.status };
+        Validator::new(rank, self.address, self.voting_power, self.moniker, self.details)
+            .with_status(status)
+            .with_commission(self.commission)
+    }
+}
+
+/// Parses a commission argument, `5%` or `0.05`, to ppm
+pub fn validate_commission(value: &str) -> Result<u64, String> {
+    parse_commission(value)
+        .filter(|commission| *commission <= 1_000_000)
+        .ok_or_else(|| format!("Invalid commission '{}', expected e.g. 5% or 0.05", value))
+}
+
+/// The address of a `- <address>` line that starts a validator
+fn record_address(line: &str) -> Option<&str> {
+    const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    let address = line.strip_prefix('-')?.trim();
+    let (prefix, data) = address.rsplit_once('1')?;
+    let valid = !prefix.is_empty()
+        && prefix.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && data.len() >= 6
+        && data.chars().all(|c| CHARSET.contains(c));
+    valid.then_some(address)
+}
+
+/// `true` for the label of a `LABEL: value` line: upper case words, no punctuation
+fn is_label(label: &str) -> bool {
+    let label = label.trim();
+    label.chars().any(|c| c.is_ascii_uppercase())
+        && label.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || matches!(c, ' ' | '_' | '-'))
+}
+
+/// Commission as ppm from `5%`, `5.00 %` or a fraction like `0.05`
+fn parse_commission(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (number, scale) = match value.strip_suffix('%') {
+        Some(percent) => (percent.trim(), 10_000.0),
+        None => (value, 1_000_000.0),
+    };
+    number.parse::<f64>().ok()
+        .filter(|rate| rate.is_finite() && *rate >= 0.0)
+        .map(|rate| (rate * scale).round() as u64)
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(from = "StoredCollection")]
 pub struct ValidatorCollection {
     timestamp: DateTime<Utc>,
     validators: Vec<Validator>,
+    /// Lowercase address to position in `validators`
+    #[serde(skip)]
+    by_address: HashMap<String, usize>,
+    /// Lowercase moniker to positions in `validators`
+    #[serde(skip)]
+    by_moniker: HashMap<String, Vec<usize>>,
+}
+
+/// What is saved of a `ValidatorCollection`, the indexes are rebuilt on load
+#[derive(serde::Deserialize)]
+struct StoredCollection {
+    timestamp: DateTime<Utc>,
+    validators: Vec<Validator>,
+}
 
+impl From<StoredCollection> for ValidatorCollection {
+    fn from(stored: StoredCollection) -> Self {
+        Self::new(stored.timestamp, stored.validators)
+    }
 }
 
 impl ValidatorCollection {
 
+    /// Creates a collection and builds its address and moniker indexes
+    pub fn new(timestamp: DateTime<Utc>, validators: Vec<Validator>) -> Self {
+        let mut collection = Self {
+            timestamp,
+            validators: Vec::with_capacity(validators.len()),
+            by_address: HashMap::with_capacity(validators.len()),
+            by_moniker: HashMap::with_capacity(validators.len()),
+        };
+        for validator in validators {
+            collection.insert(validator);
+        }
+        collection
+    }
+
     pub fn initialize_oncecell(validators: Option<ValidatorCollection>) -> OnceCell<Self> {
         let cell = OnceCell::new();
         if let Some(v) = validators {
@@ -106,7 +229,12 @@ impl ValidatorCollection {
         I: IntoIterator<Item = Validator>,
 
     {
-        Self{ validators: iter.into_iter().collect(), timestamp: Utc::now() }
+        Self::new(Utc::now(), iter.into_iter().collect())
+    }
+
+    // Getter for the time the validators were fetched
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
     }
 
     // Getter for validators (for iteration)
@@ -114,59 +242,46 @@ impl ValidatorCollection {
         self.validators.iter()
     }
 
+    /// Parses `nomic validators` output. Each validator starts with a `- <address>` line,
+    /// followed by `LABEL: value` lines in any order; unknown labels are ignored and
+    /// other lines after `DETAILS:` continue the details, up to the next label.
     pub fn import(input: String, timestamp: Option<DateTime<Utc>>) -> eyre::Result<Self> {
-        let lines: Vec<&str> = input.lines().collect();
-        let mut rank = 1;
         let mut validators = Vec::new();
+        let mut current: Option<ParsedValidator> = None;
 
-        for chunk in lines.chunks(4) {
-            if chunk.len() == 4 {
-                let mut address = String::new();
-                let mut voting_power = 0;
-                let mut moniker = String::new();
-                let mut details = String::new();
-
-                for line in chunk {
-                    let trimmed_line = line.trim();
-
-                    if trimmed_line.starts_with('-') {
-                        // Extract address (remove dash and any leading whitespace)
-                        address = trimmed_line.trim_start_matches('-').trim().to_string();
-                    } else if trimmed_line.starts_with("VOTING POWER:") {
-                        // Extract voting power
-                        let voting_power_str = trimmed_line.split("VOTING POWER:")
-                            .nth(1)
-                            .unwrap_or("")
-                            .trim();
-                        voting_power = voting_power_str.parse::<u64>().unwrap_or(0);
-                    } else if trimmed_line.starts_with("MONIKER:") {
-                        // Extract moniker
-                        moniker = trimmed_line.split("MONIKER:")
-                            .nth(1)
-                            .unwrap_or("")
-                            .trim()
-                            .to_string();
-                    } else if trimmed_line.starts_with("DETAILS:") {
-                        // Extract details
-                        details = trimmed_line.split("DETAILS:")
-                            .nth(1)
-                            .unwrap_or("")
-                            .trim()
-                            .to_string();
-                    }
+        for line in input.lines() {
+            let trimmed_line = line.trim();
+            if trimmed_line.is_empty() {
+                continue;
+            }
+
+            if let Some(address) = record_address(trimmed_line) {
+                if let Some(parsed) = current.take() {
+                    validators.push(parsed.into_validator(validators.len() as u64 + 1));
                 }
+                current = Some(ParsedValidator::new(address));
+                continue;
+            }
 
-                // Create a Validator instance and add it to the list
-                let validator = Validator::new(rank, address, voting_power, moniker, details);
-                validators.push(validator);
-                rank += 1;
+            let Some(parsed) = current.as_mut() else {
+                continue;
+            };
+            match trimmed_line.split_once(':') {
+                Some((label, value)) if is_label(label) => parsed.set(label, value.trim()),
+                _ => if parsed.in_details {
+                    parsed.details.push(' ');
+                    parsed.details.push_str(trimmed_line);
+                },
             }
         }
+        if let Some(parsed) = current.take() {
+            validators.push(parsed.into_validator(validators.len() as u64 + 1));
+        }
 
         // Set timestamp to the current time if none is provided
         let timestamp = timestamp.unwrap_or_else(Utc::now);
 
-        Ok(Self { timestamp, validators })
+        Ok(Self::new(timestamp, validators))
     }
 
 //    /// Imports validators from a string input and returns a ValidatorCollection.
@@ -210,6 +325,22 @@ impl ValidatorCollection {
         Self::import(input, timestamp)
     }
 
+    /// Validators from the on-disk cache while younger than `validator_cache_ttl`,
+    /// otherwise fetched and cached. `--refresh` always fetches, `--offline` never does.
+    pub fn cached() -> eyre::Result<Self> {
+        if !Runtime::refresh() {
+            if let Some(cached) = cache::load::<Self>("validators") {
+                if Runtime::offline() || cached.is_fresh(CONFIG.validator_cache_ttl) {
+                    return Ok(cached.value);
+                }
+            }
+        }
+        Runtime::ensure_online("fetch validators")?;
+        let collection = Self::fetch()?;
+        cache::store("validators", &collection);
+        Ok(collection)
+    }
+
     pub fn fetch() -> eyre::Result<Self> {
 
         let timestamp = Some(Utc::now());
@@ -239,7 +370,9 @@ impl ValidatorCollection {
         // Convert the command output to a string
         let output_str = String::from_utf8(output.stdout)?;
 
-        Self::import(output_str, timestamp)
+        let collection = Self::import(output_str, timestamp)?;
+        snapshot::record(&collection);
+        Ok(collection)
 
     }
     /// Creates a `ValidatorCollection` from a `Vec<Validator>`.
@@ -256,7 +389,7 @@ impl ValidatorCollection {
     /// assert_eq!(collection.len(), 1);
     /// ```
     pub fn from_vec(validators: Vec<Validator>) -> Self {
-        Self{ validators, timestamp: Utc::now()}
+        Self::new(Utc::now(), validators)
     }
 
 
@@ -275,6 +408,9 @@ impl ValidatorCollection {
     /// assert_eq!(collection.len(), 1);
     /// ```
     pub fn insert(&mut self, validator: Validator) {
+        let index = self.validators.len();
+        self.by_address.insert(validator.address().to_lowercase(), index);
+        self.by_moniker.entry(validator.moniker().to_lowercase()).or_default().push(index);
         self.validators.push(validator);
     }
 
@@ -304,168 +440,198 @@ impl ValidatorCollection {
         self.validators.is_empty()
     }
 
+    /// Position of the validator with `address`, case-insensitive
+    fn address_index(&self, address: &str) -> Option<usize> {
+        self.by_address.get(&address.to_lowercase()).copied()
+    }
+
+    /// Positions of validators whose moniker contains `search_lower`
+    fn moniker_indexes<'a>(&'a self, search_lower: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.by_moniker.iter()
+            .filter(move |(moniker, _)| moniker.contains(search_lower))
+            .flat_map(|(_, indexes)| indexes.iter().copied())
+    }
+
+    /// A new collection with the validators at `indexes`, in collection order
+    fn select(&self, mut indexes: Vec<usize>) -> Self {
+        indexes.sort_unstable();
+        indexes.dedup();
+        ValidatorCollection::new(
+            self.timestamp, // Retain the original timestamp
+            indexes.into_iter().map(|index| self.validators[index].clone()).collect(),
+        )
+    }
+
     pub fn validator(&self, address: &str) -> eyre::Result<&Validator> {
-        self.validators.iter()
-            .find(|v| v.address().to_lowercase() == address.to_lowercase())
+        self.address_index(address)
+            .map(|index| &self.validators[index])
             .ok_or_else(|| eyre::eyre!("Validator with address `{}` not found", address))
     }
 
+    /// The first validator with exactly this moniker, case-insensitive
+    pub fn validator_by_moniker(&self, moniker: &str) -> Option<&Validator> {
+        self.by_moniker.get(&moniker.to_lowercase())
+            .and_then(|indexes| indexes.first())
+            .map(|&index| &self.validators[index])
+    }
+
     /// Filters validators by address and returns a new ValidatorCollection with the original timestamp.
     pub fn filter_address(&self, search: &str) -> eyre::Result<Self> {
-        // Filter the validators based on the address
-        let filtered_validators: Vec<Validator> = self.validators.iter()
-            .filter(|validator| validator.address().eq_ignore_ascii_case(search)) // Case-insensitive match
-            .cloned()   // Cloning for new instances
-            .collect(); // Collecting into a Vec<Validator>
-
-        // Check if the filtered collection is empty
-        if filtered_validators.is_empty() {
-            return Err(eyre::eyre!("No validators found with address `{}`", search));
+        match self.address_index(search) {
+            Some(index) => Ok(self.select(vec![index])),
+            None => Err(eyre::eyre!("No validators found with address `{}`", search)),
         }
-
-        // Return a new ValidatorCollection with the original timestamp and filtered validators
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp,
-            validators: filtered_validators,
-        })
     }
 
     /// Filters validators by moniker and returns a new ValidatorCollection with the original timestamp.
     pub fn filter_moniker(&self, search: &str) -> eyre::Result<Self> {
         // Convert search term to lowercase for case-insensitive matching
         let search_lower = search.to_lowercase();
-
-        // Filter the validators based on the moniker
-        let filtered_validators: Vec<Validator> = self.validators.iter()
-            .filter(|validator| validator.moniker().to_lowercase().contains(&search_lower))
-            .cloned() // Cloning for new instances
-            .collect(); // Collecting into a Vec<Validator>
+        let indexes: Vec<usize> = self.moniker_indexes(&search_lower).collect();
 
         // Check if the filtered collection is empty
-        if filtered_validators.is_empty() {
+        if indexes.is_empty() {
             return Err(eyre::eyre!("No validators found with moniker containing `{}`", search));
         }
 
-        // Return a new ValidatorCollection with the original timestamp and filtered validators
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators: filtered_validators,
-        })
+        Ok(self.select(indexes))
+    }
+
+    /// Keeps only the validators with one of the given statuses, ranks are left unchanged.
+    pub fn filter_status(&self, statuses: &[ValidatorStatus]) -> Self {
+        let indexes = self.validators.iter()
+            .enumerate()
+            .filter(|(_, validator)| statuses.contains(&validator.status()))
+            .map(|(index, _)| index)
+            .collect();
+        self.select(indexes)
+    }
+
+    /// Keeps only the validators with a known commission of at most `max` ppm, ranks are left unchanged.
+    pub fn filter_commission(&self, max: u64) -> Self {
+        let indexes = self.validators.iter()
+            .enumerate()
+            .filter(|(_, validator)| validator.commission().is_some_and(|commission| commission <= max))
+            .map(|(index, _)| index)
+            .collect();
+        self.select(indexes)
     }
 
     /// Searches for validators by address or moniker and returns a new ValidatorCollection with the original timestamp.
     pub fn search(&self, search: &str) -> eyre::Result<Self> {
-        // Attempt to filter by exact address match first
-        let address_filtered: Vec<Validator> = self.validators.iter()
-            .filter(|validator| validator.address().eq_ignore_ascii_case(search)) // Case-insensitive match
-            .cloned()   // Cloning for new instances
-            .collect(); // Collecting into a Vec<Validator>
-
-        // If any validators were found by address, return them
-        if !address_filtered.is_empty() {
-            return Ok(ValidatorCollection {
-                timestamp: self.timestamp, // Retain the original timestamp
-                validators: address_filtered,
-            });
+        // An exact address match wins over moniker sub-matches
+        if let Some(index) = self.address_index(search) {
+            return Ok(self.select(vec![index]));
         }
 
-        // If no exact address matches were found, filter by moniker sub-match
         let search_lower = search.to_lowercase();
-        let moniker_filtered: Vec<Validator> = self.validators.iter()
-            .filter(|validator| validator.moniker().to_lowercase().contains(&search_lower))
-            .cloned()   // Cloning for new instances
-            .collect(); // Collecting into a Vec<Validator>
+        let mut indexes: Vec<usize> = self.moniker_indexes(&search_lower).collect();
+
+        // Fall back to near misses, so a typo still finds the validator
+        if indexes.is_empty() {
+            indexes = self.fuzzy_indexes(search);
+        }
 
         // Check if any validators were found by moniker
-        if moniker_filtered.is_empty() {
-            return Err(eyre::eyre!("No validators found with address or moniker matching `{}`", search));
+        if indexes.is_empty() {
+            return Err(eyre::eyre!(
+                "No validators found with address or moniker matching `{}`.{}",
+                search, fuzzy::did_you_mean(&self.suggest(search)),
+            ));
+        }
+
+        Ok(self.select(indexes))
+    }
+
+    /// Positions of validators ranked by how well their moniker or address matches `search`, best first
+    fn fuzzy_indexes(&self, search: &str) -> Vec<usize> {
+        let monikers: Vec<&str> = self.validators.iter().map(|v| v.moniker()).collect();
+        let mut indexes = fuzzy::rank(&monikers, search);
+        if indexes.is_empty() {
+            let addresses: Vec<&str> = self.validators.iter().map(|v| v.address()).collect();
+            indexes = fuzzy::rank(&addresses, search);
         }
+        indexes
+    }
 
-        // Return the new ValidatorCollection with the original timestamp and filtered results
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators: moniker_filtered,
-        })
+    /// Monikers close to `search`, for error messages
+    fn suggest(&self, search: &str) -> Vec<String> {
+        let monikers: Vec<&str> = self.validators.iter().map(|v| v.moniker()).collect();
+        fuzzy::suggest(&monikers, search, 3)
+    }
+
+    /// Resolves `search` to a single validator by exact address or moniker. Near misses
+    /// are offered for confirmation on a terminal, otherwise fail suggesting them.
+    pub fn find_one(&self, search: &str) -> eyre::Result<&Validator> {
+        if let Some(index) = self.address_index(search) {
+            return Ok(&self.validators[index]);
+        }
+        if let Some(validator) = self.validator_by_moniker(search) {
+            return Ok(validator);
+        }
+
+        let indexes = self.fuzzy_indexes(search);
+        if indexes.is_empty() {
+            return Err(eyre::eyre!(
+                "No validator found with address or moniker matching `{}`.{}",
+                search, fuzzy::did_you_mean(&self.suggest(search)),
+            ));
+        }
+
+        let options: Vec<String> = indexes.iter()
+            .map(|&index| {
+                let validator = &self.validators[index];
+                format!("{} ({})", validator.moniker(), validator.address())
+            })
+            .collect();
+        let choice = fuzzy::confirm("validator", search, &options)?;
+        Ok(&self.validators[indexes[choice]])
     }
 
     /// Searches for multiple validators based on a list of search terms and returns a new ValidatorCollection with the original timestamp.
     pub fn search_multi(&self, searches: Vec<String>) -> eyre::Result<Self> {
-        let mut results = Vec::new(); // Initialize a vector to store results
+        let mut indexes = Vec::new();
 
-        // Loop through each search term
         for search in searches {
-            let search_lower = search.to_lowercase();
-
-            // Check each validator against the search term
-            for validator in &self.validators {
-                // Check for exact address match
-                if validator.address().eq_ignore_ascii_case(&search) {
-                    results.push(validator.clone());
-                } 
-                // Check for moniker match
-                if validator.moniker().to_lowercase().contains(&search_lower) {
-                    // Push to results if the validator matches the search
-                    results.push(validator.clone());
-                }
-            }
+            indexes.extend(self.address_index(&search));
+            indexes.extend(self.moniker_indexes(&search.to_lowercase()));
         }
 
         // Check if any results were found
-        if results.is_empty() {
+        if indexes.is_empty() {
             return Err(eyre::eyre!("No validators found matching any of the search terms"));
         }
 
-        // Return a new ValidatorCollection with the original timestamp and the filtered results
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators: results,
-        })
+        Ok(self.select(indexes))
     }
 
     /// Filters validators based on a vector of addresses and retains the original timestamp.
     pub fn filter_addresses(&self, searches: Vec<String>) -> eyre::Result<Self> {
-        // Create a new vector by filtering the existing validators
-        let validators: Vec<Validator> = self.validators.iter()
-            .filter(|validator| {
-                // Check if the validator's address matches any address in the searches vector
-                searches.iter().any(|search| validator.address().eq_ignore_ascii_case(search))
-            })
-            .cloned() // Cloning for new instances
-            .collect(); // Collecting into a Vec<Validator>
+        let indexes: Vec<usize> = searches.iter()
+            .filter_map(|search| self.address_index(search))
+            .collect();
 
         // Check if the new collection is empty
-        if validators.is_empty() {
+        if indexes.is_empty() {
             return Err(eyre::eyre!("No validators found with the specified addresses."));
         }
 
-        // Return the new ValidatorCollection with the filtered results and original timestamp
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators,
-        })
+        Ok(self.select(indexes))
     }
 
     /// Filters validators based on a vector of moniker searches and retains the original timestamp.
     pub fn filter_monikers(&self, searches: Vec<String>) -> eyre::Result<Self> {
-        // Create a new vector by filtering the existing validators
-        let validators: Vec<Validator> = self.validators.iter()
-            .filter(|validator| {
-                // Check if the validator's moniker matches any of the searches
-                searches.iter().any(|search| validator.moniker().to_lowercase().contains(&search.to_lowercase()))
-            })
-            .cloned() // Cloning for new instances
-            .collect(); // Collecting into a Vec<Validator>
+        let mut indexes = Vec::new();
+        for search in searches {
+            indexes.extend(self.moniker_indexes(&search.to_lowercase()));
+        }
 
         // Check if the new collection is empty
-        if validators.is_empty() {
+        if indexes.is_empty() {
             return Err(eyre::eyre!("No validators found with any of the specified monikers."));
         }
 
-        // Return the new ValidatorCollection with the filtered results and original timestamp
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators,
-        })
+        Ok(self.select(indexes))
     }
 
     /// Returns the top `n` validators sorted by voting power in descending order.
@@ -483,10 +649,7 @@ impl ValidatorCollection {
         top_validators.validators.truncate(count);
 
         // Return the modified ValidatorCollection with the original timestamp
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators: top_validators.validators,
-        })
+        Ok(ValidatorCollection::new(self.timestamp, top_validators.validators))
     }
 
     /// Returns the bottom `n` validators sorted by voting power in ascending order.
@@ -504,10 +667,7 @@ impl ValidatorCollection {
         bottom_validators.validators.truncate(count);
 
         // Return the modified ValidatorCollection with the original timestamp
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators: bottom_validators.validators,
-        })
+        Ok(ValidatorCollection::new(self.timestamp, bottom_validators.validators))
     }
 
     pub fn skip_top(&self, n: Option<usize>) -> eyre::Result<Self> {
@@ -530,10 +690,7 @@ impl ValidatorCollection {
         remaining_validators.validators.drain(..count);
 
         // Return the modified ValidatorCollection with the original timestamp
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators: remaining_validators.validators,
-        })
+        Ok(ValidatorCollection::new(self.timestamp, remaining_validators.validators))
     }
 
     pub fn skip_bottom(&self, n: Option<usize>) -> eyre::Result<Self> {
@@ -556,10 +713,33 @@ impl ValidatorCollection {
         remaining_validators.validators.drain(..count);
 
         // Return the modified ValidatorCollection with the original timestamp
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators: remaining_validators.validators,
-        })
+        Ok(ValidatorCollection::new(self.timestamp, remaining_validators.validators))
+    }
+
+    /// Skips the validators that together hold the top `percent` of the total voting power,
+    /// i.e. every validator needed to reach that share when counting from the top.
+    pub fn skip_top_voting_power(&self, percent: f64) -> eyre::Result<Self> {
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(eyre!("Percentage must be between 0 and 100, got {}", percent));
+        }
+
+        let mut remaining_validators = self.clone();
+        remaining_validators.validators.sort_by_key(|v| std::cmp::Reverse(v.voting_power()));
+
+        let total: u128 = remaining_validators.validators.iter().map(|v| v.voting_power() as u128).sum();
+        let threshold = (total as f64 * percent / 100.0) as u128;
+
+        let mut cumulative: u128 = 0;
+        let skip = remaining_validators.validators.iter()
+            .take_while(|v| {
+                let before = cumulative;
+                cumulative += v.voting_power() as u128;
+                before < threshold
+            })
+            .count();
+        remaining_validators.validators.drain(..skip);
+
+        Ok(ValidatorCollection::new(self.timestamp, remaining_validators.validators))
     }
 
     /// Returns a random selection of validators, optionally skipping some from the top and bottom.
@@ -590,10 +770,7 @@ impl ValidatorCollection {
         random_validators.validators.truncate(count);
 
         // Return the modified ValidatorCollection with the random validators and original timestamp
-        Ok(ValidatorCollection {
-            timestamp: self.timestamp, // Retain the original timestamp
-            validators: random_validators.validators,
-        })
+        Ok(ValidatorCollection::new(self.timestamp, random_validators.validators))
     }
 
     /// Returns a random selection of validators based on percentage, skipping some from the top and bottom.
@@ -629,6 +806,12 @@ impl ValidatorCollection {
             output.push_str(&format!("- {}\n", validator.address()));
             output.push_str(&format!("    VOTING POWER: {}\n", validator.voting_power()));
             output.push_str(&format!("    MONIKER: {}\n", validator.moniker()));
+            if validator.status() != ValidatorStatus::Unknown {
+                output.push_str(&format!("    STATUS: {}\n", validator.status()));
+            }
+            if let Some(commission) = validator.commission() {
+                output.push_str(&format!("    COMMISSION: {}%\n", NumberDisplay::new(commission).scale(4).decimal_places(2).format()));
+            }
 
             // Include details if specified
             if include_details {
@@ -641,12 +824,18 @@ impl ValidatorCollection {
     }
 
     pub fn table(&self) -> String {
+        // Status and commission only when `nomic validators` reports them
+        let show_status = self.iter().any(|v| v.status() != ValidatorStatus::Unknown);
+        let show_commission = self.iter().any(|v| v.commission().is_some());
+
         let mut rows: Vec<TableColumns> = Vec::new();
         rows.push(TableColumns::new(vec![
             "Rank",
             "Address",
             "Power",
             "Moniker",
+            "Status",
+            "Commission",
         ]));
         for validator in self.iter() {
             rows.push(TableColumns::new(vec![
@@ -654,14 +843,25 @@ impl ValidatorCollection {
                 &validator.address(),
                 &NumberDisplay::new(validator.voting_power()).scale(6).decimal_places(0).format(),
                 &truncate_with_ellipsis(validator.moniker(), 24),
+                &validator.status().to_string(),
+                &validator.commission()
+                    .map(|c| format!("{}%", NumberDisplay::new(c).scale(4).decimal_places(2).format()))
+                    .unwrap_or_else(|| "-".to_string()),
             ]));
         };
 
         let mut builder = Builder::default();
         for row in &rows {
-            builder.push_record([
+            let mut record = vec![
                 row.cell0.clone(), row.cell1.clone(), row.cell2.clone(), row.cell3.clone()
-            ]);
+            ];
+            if show_status {
+                record.push(row.cell4.clone());
+            }
+            if show_commission {
+                record.push(row.cell5.clone());
+            }
+            builder.push_record(record);
         }
 
         let mut table = builder.build();
@@ -674,6 +874,12 @@ impl ValidatorCollection {
             .with(Modify::new(Cell::new(0, 2)).with(Border::new().set_bottom('-')))
             .with(Modify::new(Cell::new(0, 3)).with(Border::new().set_bottom('-')))
             ;
+        if show_status || show_commission {
+            table.with(Modify::new(Rows::first()).with(Border::new().set_bottom('-')));
+        }
+        if show_commission {
+            table.with(Modify::new(Columns::last()).with(Alignment::right()));
+        }
 
         table.to_string()
 
@@ -758,6 +964,10 @@ impl ValidatorCollection {
             record.insert("VOTING POWER".to_string(), serde_json::Value::Number(validator.voting_power().into()));
             record.insert("MONIKER".to_string(),      serde_json::Value::String(validator.moniker().to_string()));
             record.insert("RANK".to_string(),         serde_json::Value::Number(validator.rank().into()));
+            record.insert("STATUS".to_string(),       serde_json::Value::String(validator.status().to_string()));
+            if let Some(commission) = validator.commission() {
+                record.insert("COMMISSION".to_string(), serde_json::Value::Number(commission.into()));
+            }
 
             if include_details {
                 record.insert("DETAILS".to_string(),  serde_json::Value::String(validator.details().to_string()));
@@ -925,6 +1135,8 @@ impl Clone for ValidatorCollection {
         Self {
             timestamp: self.timestamp, // Clone the timestamp
             validators: self.validators.clone(), // Clone the vector of validators
+            by_address: self.by_address.clone(),
+            by_moniker: self.by_moniker.clone(),
         }
     }
 
@@ -948,7 +1160,7 @@ impl FromIterator<Validator> for ValidatorCollection {
     fn from_iter<T: IntoIterator<Item = Validator>>(iter: T) -> Self {
         let validators: Vec<Validator> = iter.into_iter().collect(); // Collect the validators into a vector
         let timestamp = Utc::now(); // Set the timestamp to the current time or any default value you prefer
-        Self { timestamp, validators } // Wrap the vector and timestamp in the ValidatorCollection
+        Self::new(timestamp, validators) // Wrap the vector and timestamp in the ValidatorCollection
     }
 }
 
@@ -995,3 +1207,70 @@ impl IntoIterator for ValidatorCollection {
         self.validators.into_iter() // Consumes self and returns an iterator over the contained Validators.
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+- nomic1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu
+      VOTING POWER: 2000
+      MONIKER: First
+      DETAILS: Runs in two regions.
+      - audited
+      WEBSITE: https://first.example
+      COMMISSION: 5%
+- nomic1zg69w7y6hn0aqy352euf40x77qfrg4ncn27dxs
+      VOTING POWER: 1000
+      MONIKER: Second
+      DETAILS: Contact: ops@second.example
+      STATUS: jailed
+      COMMISSION: 0.1
+";
+
+    #[test]
+    fn import_reads_labels_in_any_order() {
+        let collection = ValidatorCollection::import(INPUT.to_string(), None).unwrap();
+        assert_eq!(collection.len(), 2);
+
+        let first = collection.validator("nomic1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu").unwrap();
+        assert_eq!(first.voting_power(), 2000);
+        assert_eq!(first.moniker(), "First");
+        assert_eq!(first.commission(), Some(50_000));
+
+        let second = collection.validator("nomic1zg69w7y6hn0aqy352euf40x77qfrg4ncn27dxs").unwrap();
+        assert_eq!(second.status(), ValidatorStatus::Jailed);
+        assert_eq!(second.commission(), Some(100_000));
+    }
+
+    #[test]
+    fn details_continue_until_the_next_label() {
+        let collection = ValidatorCollection::import(INPUT.to_string(), None).unwrap();
+        let first = collection.validator("nomic1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu").unwrap();
+        // A `- ` line that is not an address continues the details, an unknown label ends them
+        assert_eq!(first.details(), "Runs in two regions. - audited");
+
+        let second = collection.validator("nomic1zg69w7y6hn0aqy352euf40x77qfrg4ncn27dxs").unwrap();
+        assert_eq!(second.details(), "Contact: ops@second.example");
+    }
+
+    #[test]
+    fn filter_commission_keeps_known_commissions_up_to_max() {
+        let input = format!("{}- nomic1yg3nqg9qhxvnaz8gl5xslhfh4r70mljgzmt4vq\n      MONIKER: Third\n", INPUT);
+        let collection = ValidatorCollection::import(input, None).unwrap();
+        assert_eq!(collection.len(), 3);
+
+        let filtered = collection.filter_commission(validate_commission("5%").unwrap());
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered.iter().next().unwrap().moniker(), "First");
+        assert_eq!(collection.filter_commission(1_000_000).len(), 2);
+    }
+
+    #[test]
+    fn validate_commission_rejects_nonsense() {
+        assert_eq!(validate_commission("0.05"), Ok(50_000));
+        assert_eq!(validate_commission("12.5%"), Ok(125_000));
+        assert!(validate_commission("150%").is_err());
+        assert!(validate_commission("five").is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
+use crate::functions::NumberDisplay;
+use crate::functions::TableColumns;
+use crate::validators::ValidatorCollection;
+use eyre::Result;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use tabled::builder::Builder;
+use tabled::settings::{Alignment, Border, Color, Modify, Style};
+use tabled::settings::object::{Columns, Rows};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One validator's before and after, `None` where it was not in that set
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidatorChange {
+    pub address:             String,
+    pub kind:                ChangeKind,
+    pub moniker_before:      Option<String>,
+    pub moniker_after:       Option<String>,
+    pub rank_before:         Option<u64>,
+    pub rank_after:          Option<u64>,
+    pub voting_power_before: Option<u64>,
+    pub voting_power_after:  Option<u64>,
+}
+
+impl ValidatorChange {
+
+    pub fn moniker(&self) -> &str {
+        self.moniker_after.as_deref()
+            .or(self.moniker_before.as_deref())
+            .unwrap_or_default()
+    }
+
+    pub fn moniker_changed(&self) -> bool {
+        self.kind == ChangeKind::Changed && self.moniker_before != self.moniker_after
+    }
+
+    /// Positive when the validator moved up
+    pub fn rank_delta(&self) -> i64 {
+        match (self.rank_before, self.rank_after) {
+            (Some(before), Some(after)) => before as i64 - after as i64,
+            _ => 0,
+        }
+    }
+
+    pub fn voting_power_delta(&self) -> i128 {
+        self.voting_power_after.unwrap_or(0) as i128 - self.voting_power_before.unwrap_or(0) as i128
+    }
+}
+
+/// Differences between two validator sets
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidatorDiff {
+    pub from:    DateTime<Utc>,
+    pub to:      DateTime<Utc>,
+    pub changes: Vec<ValidatorChange>,
+}
+
+impl ValidatorDiff {
+
+    /// Validators whose rank, voting power and moniker are all unchanged are left out
+    pub fn new(from: &ValidatorCollection, to: &ValidatorCollection) -> Self {
+        let before: HashMap<&str, _> = from.iter().map(|v| (v.address(), v)).collect();
+        let after: HashMap<&str, _> = to.iter().map(|v| (v.address(), v)).collect();
+
+        let mut changes: Vec<ValidatorChange> = Vec::new();
+        for validator in to.iter() {
+            let previous = before.get(validator.address());
+            let kind = match previous {
+                None => ChangeKind::Added,
+                Some(previous) if previous.rank() != validator.rank()
+                    || previous.voting_power() != validator.voting_power()
+                    || previous.moniker() != validator.moniker() => ChangeKind::Changed,
+                Some(_) => continue,
+            };
+            changes.push(ValidatorChange {
+                address:             validator.address().to_string(),
+                kind,
+                moniker_before:      previous.map(|v| v.moniker().to_string()),
+                moniker_after:       Some(validator.moniker().to_string()),
+                rank_before:         previous.map(|v| v.rank()),
+                rank_after:          Some(validator.rank()),
+                voting_power_before: previous.map(|v| v.voting_power()),
+                voting_power_after:  Some(validator.voting_power()),
+            });
+        }
+        for validator in from.iter().filter(|v| !after.contains_key(v.address())) {
+            changes.push(ValidatorChange {
+                address:             validator.address().to_string(),
+                kind:                ChangeKind::Removed,
+                moniker_before:      Some(validator.moniker().to_string()),
+                moniker_after:       None,
+                rank_before:         Some(validator.rank()),
+                rank_after:          None,
+                voting_power_before: Some(validator.voting_power()),
+                voting_power_after:  None,
+            });
+        }
+        changes.sort_by_key(|change| change.rank_after.or(change.rank_before).unwrap_or(u64::MAX));
+
+        Self { from: from.timestamp(), to: to.timestamp(), changes }
+    }
+
+    /// Keeps only the changes for `addresses`
+    pub fn retain(&mut self, addresses: &HashSet<String>) {
+        self.changes.retain(|change| addresses.contains(&change.address));
+    }
+
+    pub fn table(&self) -> String {
+        let rank = |rank: Option<u64>| rank.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string());
+        let nom = |n: i128| {
+            let formatted = NumberDisplay::new(n.unsigned_abs().min(u64::MAX as u128) as u64)
+                .scale(6).decimal_places(0).format();
+            if n < 0 { format!("-{}", formatted) } else if n > 0 { format!("+{}", formatted) } else { formatted }
+        };
+
+        let mut rows: Vec<TableColumns> = Vec::new();
+        rows.push(TableColumns::new(vec![
+            "Change", "Rank", "Rank Delta", "Validator Address", "Moniker", "Voting Power", "Delta",
+        ]));
+        for change in &self.changes {
+            let moniker = if change.moniker_changed() {
+                format!("{} -> {}", change.moniker_before.as_deref().unwrap_or_default(), change.moniker())
+            } else {
+                change.moniker().to_string()
+            };
+            let rank_delta = match change.rank_delta() {
+                0 => String::new(),
+                delta if delta > 0 => format!("+{}", delta),
+                delta => delta.to_string(),
+            };
+            rows.push(TableColumns::new(vec![
+                &format!("{:?}", change.kind).to_lowercase(),
+                &rank(change.rank_after.or(change.rank_before)),
+                &rank_delta,
+                &change.address,
+                &moniker,
+                &nom(change.voting_power_after.or(change.voting_power_before).unwrap_or(0) as i128),
+                &nom(change.voting_power_delta()),
+            ]));
+        }
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([
+                row.cell0.clone(), row.cell1.clone(), row.cell2.clone(), row.cell3.clone(),
+                row.cell4.clone(), row.cell5.clone(), row.cell6.clone(),
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::blank())
+            .with(Modify::new(Columns::new(1..3)).with(Alignment::right()))
+            .with(Modify::new(Columns::new(5..)).with(Alignment::right()))
+            .with(Modify::new(Rows::first()).with(Border::new().set_bottom('-')))
+            ;
+        for (index, change) in self.changes.iter().enumerate() {
+            match change.kind {
+                ChangeKind::Added   => { table.with(Modify::new(Rows::single(index + 1)).with(Color::FG_GREEN)); },
+                ChangeKind::Removed => { table.with(Modify::new(Rows::single(index + 1)).with(Color::FG_RED)); },
+                ChangeKind::Changed => {},
+            }
+        }
+
+        let local = |time: DateTime<Utc>| time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string();
+        format!("{} -> {}\n\n{}", local(self.from), local(self.to), table)
+    }
+
+    pub fn print(&self, format: Option<OutputFormat>) -> Result<()> {
+        match format {
+            Some(OutputFormat::Json) => println!("{}", serde_json::to_string(self)?),
+            Some(OutputFormat::JsonPretty) => println!("{}", serde_json::to_string_pretty(self)?),
+            Some(OutputFormat::Table) | None => {
+                if self.changes.is_empty() {
+                    println!("\nNo changes\n");
+                } else {
+                    println!("\n{}\n", self.table());
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Enum to represent output formats
+#[derive(Debug, Clone, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    JsonPretty,
+    Table,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json"        => Ok(OutputFormat::Json),
+            "json-pretty" => Ok(OutputFormat::JsonPretty),
+            "table"       => Ok(OutputFormat::Table),
+            _ => Err(format!("Invalid output format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            OutputFormat::Json       => "json",
+            OutputFormat::JsonPretty => "json-pretty",
+            OutputFormat::Table      => "table",
+        };
+        write!(f, "{}", output)
+    }
+}
@@ -1,9 +1,19 @@
 mod cli;
 mod validator;
 mod collection;
+mod diff;
+mod snapshot;
+mod stats;
 
 pub use cli::Cli;
 pub use collection::initialize_validators;
 pub use collection::OutputFormat;
 pub use collection::ValidatorCollection;
+pub use collection::validate_commission;
+pub use diff::OutputFormat as DiffOutputFormat;
+pub use diff::ValidatorDiff;
+pub use snapshot::Snapshot;
+pub use stats::OutputFormat as StatsOutputFormat;
+pub use stats::ValidatorStats;
 pub use validator::Validator;
+pub use validator::ValidatorStatus;
@@ -0,0 +1,155 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use crate::global::CONFIG;
+use crate::global::PROFILES_DIR;
+use crate::validators::ValidatorCollection;
+use eyre::eyre;
+use eyre::Result;
+use eyre::WrapErr;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::warn;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+const FILE_PREFIX: &str = "validators-";
+const FILE_SUFFIX: &str = ".json.gz";
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Saves a snapshot of a freshly fetched validator set and prunes the expired ones.
+/// Failures are only logged, a fetch should not fail because of a snapshot.
+pub fn record(collection: &ValidatorCollection) {
+    if CONFIG.snapshot_retention == 0 {
+        return;
+    }
+    if let Err(e) = Snapshot::save(collection) {
+        warn!("Failed to save validator snapshot: {}", e);
+    }
+    if let Err(e) = Snapshot::prune(CONFIG.snapshot_retention) {
+        warn!("Failed to prune validator snapshots: {}", e);
+    }
+}
+
+/// A validator set saved as gzipped JSON in `PROFILES_DIR/validator_snapshots`
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub timestamp: DateTime<Utc>,
+    pub path:      PathBuf,
+}
+
+impl Snapshot {
+
+    pub fn dir() -> PathBuf {
+        PROFILES_DIR.join("validator_snapshots")
+    }
+
+    pub fn save(collection: &ValidatorCollection) -> Result<Self> {
+        let dir = Self::dir();
+        fs::create_dir_all(&dir)
+            .wrap_err_with(|| format!("Failed to create snapshot directory {:?}", dir))?;
+
+        let timestamp = collection.timestamp();
+        let path = dir.join(format!("{}{}{}", FILE_PREFIX, timestamp.format(TIMESTAMP_FORMAT), FILE_SUFFIX));
+
+        let json = serde_json::to_vec(collection)?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&json)?;
+        fs::write(&path, encoder.finish()?)
+            .wrap_err_with(|| format!("Failed to write snapshot to {:?}", path))?;
+
+        Ok(Self { timestamp, path })
+    }
+
+    pub fn load(&self) -> Result<ValidatorCollection> {
+        let bytes = fs::read(&self.path)
+            .wrap_err_with(|| format!("Failed to read snapshot at {:?}", self.path))?;
+        let mut json = String::new();
+        GzDecoder::new(bytes.as_slice()).read_to_string(&mut json)
+            .wrap_err_with(|| format!("Failed to decompress snapshot at {:?}", self.path))?;
+        serde_json::from_str(&json)
+            .wrap_err_with(|| format!("Failed to parse snapshot at {:?}", self.path))
+    }
+
+    /// All snapshots, oldest first
+    pub fn list() -> Result<Vec<Self>> {
+        let dir = Self::dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots: Vec<Self> = fs::read_dir(&dir)?
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let stamp = name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+                let timestamp = NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT).ok()?.and_utc();
+                Some(Self { timestamp, path: entry.path() })
+            })
+            .collect();
+        snapshots.sort_by_key(|snapshot| snapshot.timestamp);
+        Ok(snapshots)
+    }
+
+    /// Removes snapshots older than `retention_days`, always keeping the latest one
+    pub fn prune(retention_days: u64) -> Result<usize> {
+        let cutoff = Utc::now() - Duration::days(retention_days.min(i64::MAX as u64) as i64);
+        let mut snapshots = Self::list()?;
+        snapshots.pop();
+
+        let mut removed = 0;
+        for snapshot in snapshots.iter().filter(|snapshot| snapshot.timestamp < cutoff) {
+            fs::remove_file(&snapshot.path)
+                .wrap_err_with(|| format!("Failed to remove snapshot {:?}", snapshot.path))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Finds a snapshot by `latest`, `~N` (N snapshots before the latest),
+    /// or the newest one taken at or before a date or time (local time unless RFC 3339)
+    pub fn find(selector: &str) -> Result<Self> {
+        let mut snapshots = Self::list()?;
+        if snapshots.is_empty() {
+            return Err(eyre!("No validator snapshots in {:?}", Self::dir()));
+        }
+
+        let selector = selector.trim();
+        if selector.eq_ignore_ascii_case("latest") {
+            return Ok(snapshots.pop().unwrap());
+        }
+        if let Some(back) = selector.strip_prefix('~') {
+            let back: usize = back.parse()
+                .map_err(|_| eyre!("Invalid snapshot offset: {}", selector))?;
+            let index = snapshots.len().checked_sub(back + 1)
+                .ok_or_else(|| eyre!("Only {} snapshots available", snapshots.len()))?;
+            return Ok(snapshots.swap_remove(index));
+        }
+
+        let time = parse_time(selector)?;
+        snapshots.into_iter()
+            .rev()
+            .find(|snapshot| snapshot.timestamp <= time)
+            .ok_or_else(|| eyre!("No snapshot at or before {}", selector))
+    }
+}
+
+/// A bare date means the end of that day
+fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"].iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+                .and_then(|date| date.and_hms_opt(23, 59, 59))
+        })
+        .ok_or_else(|| eyre!("Invalid snapshot selector: {} (use latest, ~N, or a date/time)", value))?;
+
+    Local.from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| eyre!("Invalid local time: {}", value))
+}
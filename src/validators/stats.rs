@@ -0,0 +1,279 @@
+use clap::ValueEnum;
+use crate::functions::NumberDisplay;
+use crate::functions::TableColumns;
+use crate::validators::ValidatorCollection;
+use eyre::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use tabled::builder::Builder;
+use tabled::settings::{Alignment, Border, Modify, Style};
+use tabled::settings::object::{Columns, Rows};
+
+/// Cumulative voting power share of the top `count` validators
+#[derive(Clone, Debug, Serialize)]
+pub struct TopShare {
+    pub count: usize,
+    /// ppm of the total voting power
+    pub share: u64,
+}
+
+/// Where one of our validators sits on the cumulative voting power curve
+#[derive(Clone, Debug, Serialize)]
+pub struct Position {
+    pub rank:         usize,
+    pub address:      String,
+    pub moniker:      String,
+    pub voting_power: u64,
+    /// ppm of the total voting power
+    pub share:        u64,
+    /// ppm of the total voting power held by this validator and every validator above it
+    pub cumulative:   u64,
+    /// Names of our profiles delegating to this validator
+    pub profiles:     Vec<String>,
+}
+
+/// Decentralization metrics for the validator set, shares in ppm
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidatorStats {
+    pub validators:   usize,
+    pub voting_power: u64,
+    /// Fewest validators that together hold more than a third of the voting power
+    pub nakamoto:     usize,
+    /// Gini coefficient of the voting power distribution, in ppm
+    pub gini:         u64,
+    pub top:          Vec<TopShare>,
+    pub ours:         Vec<Position>,
+}
+
+/// Fewest validators that together hold more than a third of the voting power, 0 without any
+fn nakamoto(powers: &[u64]) -> usize {
+    let mut powers = powers.to_vec();
+    powers.sort_unstable_by(|a, b| b.cmp(a));
+    let total: u128 = powers.iter().map(|&power| power as u128).sum();
+
+    let mut cumulative: u128 = 0;
+    for (index, power) in powers.iter().enumerate() {
+        cumulative += *power as u128;
+        if cumulative * 3 > total {
+            return index + 1;
+        }
+    }
+    0
+}
+
+/// Gini coefficient of the voting powers in ppm, 0 when they are equal and close to
+/// 1_000_000 when one validator holds everything
+fn gini(powers: &[u64]) -> u64 {
+    let mut powers = powers.to_vec();
+    powers.sort_unstable();
+    let n = powers.len() as u128;
+    let total: u128 = powers.iter().map(|&power| power as u128).sum();
+    if n == 0 || total == 0 {
+        return 0;
+    }
+
+    // G = 2 * sum(i * x_i) / (n * sum(x)) - (n + 1) / n, x ascending and i from 1
+    let weighted: u128 = powers.iter()
+        .enumerate()
+        .map(|(i, &power)| (i as u128 + 1) * power as u128)
+        .sum();
+    let gini = (2 * weighted * 1_000_000 / (n * total)) as i128
+        - ((n + 1) * 1_000_000 / n) as i128;
+    gini.max(0) as u64
+}
+
+impl ValidatorStats {
+
+    /// `delegated` maps validator addresses to the names of our profiles delegating to them
+    pub fn new(collection: &ValidatorCollection, top: &[usize], delegated: &HashMap<String, Vec<String>>) -> Self {
+        let mut powers: Vec<(&str, &str, u64)> = collection.iter()
+            .map(|v| (v.address(), v.moniker(), v.voting_power()))
+            .collect();
+        powers.sort_by_key(|(_, _, power)| std::cmp::Reverse(*power));
+
+        let total: u128 = powers.iter().map(|(_, _, power)| *power as u128).sum();
+        let ppm = |part: u128| if total == 0 { 0 } else { (part * 1_000_000 / total) as u64 };
+
+        let mut cumulative: u128 = 0;
+        let cumulatives: Vec<u128> = powers.iter()
+            .map(|(_, _, power)| {
+                cumulative += *power as u128;
+                cumulative
+            })
+            .collect();
+        let voting_powers: Vec<u64> = powers.iter().map(|(_, _, power)| *power).collect();
+
+        let top = top.iter()
+            .map(|&count| TopShare {
+                count,
+                share: match count.min(powers.len()) {
+                    0 => 0,
+                    n => ppm(cumulatives[n - 1]),
+                },
+            })
+            .collect();
+
+        let ours = powers.iter()
+            .enumerate()
+            .filter_map(|(index, (address, moniker, power))| {
+                delegated.get(*address).map(|profiles| Position {
+                    rank:         index + 1,
+                    address:      address.to_string(),
+                    moniker:      moniker.to_string(),
+                    voting_power: *power,
+                    share:        ppm(*power as u128),
+                    cumulative:   ppm(cumulatives[index]),
+                    profiles:     profiles.clone(),
+                })
+            })
+            .collect();
+
+        Self {
+            validators:   powers.len(),
+            voting_power: total.min(u64::MAX as u128) as u64,
+            nakamoto:     nakamoto(&voting_powers),
+            gini:         gini(&voting_powers),
+            top,
+            ours,
+        }
+    }
+
+    pub fn table(&self) -> String {
+        let percent = |ppm: u64| format!("{}%", NumberDisplay::new(ppm).scale(4).decimal_places(2).format());
+
+        let mut summary = Builder::default();
+        summary.push_record(["Validators".to_string(), self.validators.to_string()]);
+        summary.push_record([
+            "Voting Power".to_string(),
+            NumberDisplay::new(self.voting_power).scale(6).decimal_places(0).format(),
+        ]);
+        summary.push_record(["Nakamoto Coefficient".to_string(), self.nakamoto.to_string()]);
+        summary.push_record([
+            "Gini Coefficient".to_string(),
+            NumberDisplay::new(self.gini).scale(6).decimal_places(4).format(),
+        ]);
+        for top in &self.top {
+            summary.push_record([format!("Top {} Share", top.count), percent(top.share)]);
+        }
+        let mut summary = summary.build();
+        summary.with(Style::blank())
+            .with(Modify::new(Columns::single(1)).with(Alignment::right()));
+
+        let mut rows: Vec<TableColumns> = Vec::new();
+        rows.push(TableColumns::new(vec![
+            "Rank", "Validator Address", "Moniker", "Share", "Cumulative", "Profiles",
+        ]));
+        for position in &self.ours {
+            rows.push(TableColumns::new(vec![
+                &position.rank.to_string(),
+                &position.address,
+                &position.moniker,
+                &percent(position.share),
+                &percent(position.cumulative),
+                &position.profiles.join(", "),
+            ]));
+        }
+
+        let mut builder = Builder::default();
+        for row in &rows {
+            builder.push_record([
+                row.cell0.clone(), row.cell1.clone(), row.cell2.clone(),
+                row.cell3.clone(), row.cell4.clone(), row.cell5.clone(),
+            ]);
+        }
+        let mut ours = builder.build();
+        ours.with(Style::blank())
+            .with(Modify::new(Columns::single(0)).with(Alignment::right()))
+            .with(Modify::new(Columns::new(3..5)).with(Alignment::right()))
+            .with(Modify::new(Rows::first()).with(Border::new().set_bottom('-')))
+            ;
+
+        if self.ours.is_empty() {
+            format!("{}\n\nNone of our profiles are delegating.", summary)
+        } else {
+            format!("{}\n\nOur validators\n\n{}", summary, ours)
+        }
+    }
+
+    pub fn print(&self, format: Option<OutputFormat>) -> Result<()> {
+        match format {
+            Some(OutputFormat::Json) => println!("{}", serde_json::to_string(self)?),
+            Some(OutputFormat::JsonPretty) => println!("{}", serde_json::to_string_pretty(self)?),
+            Some(OutputFormat::Table) | None => println!("\n{}\n", self.table()),
+        }
+        Ok(())
+    }
+}
+
+/// Enum to represent output formats
+#[derive(Debug, Clone, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    JsonPretty,
+    Table,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json"        => Ok(OutputFormat::Json),
+            "json-pretty" => Ok(OutputFormat::JsonPretty),
+            "table"       => Ok(OutputFormat::Table),
+            _ => Err(format!("Invalid output format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            OutputFormat::Json       => "json",
+            OutputFormat::JsonPretty => "json-pretty",
+            OutputFormat::Table      => "table",
+        };
+        write!(f, "{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nakamoto_needs_more_than_a_third() {
+        assert_eq!(nakamoto(&[]), 0);
+        assert_eq!(nakamoto(&[0, 0]), 0);
+        // Exactly 33.33% is not more than a third
+        assert_eq!(nakamoto(&[3_333, 3_333, 3_333, 1]), 2);
+        assert_eq!(nakamoto(&[3_333, 3_334, 3_333]), 1);
+        assert_eq!(nakamoto(&[1, 1, 1]), 2);
+        assert_eq!(nakamoto(&[10, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]), 1);
+    }
+
+    #[test]
+    fn gini_of_equal_powers_is_zero() {
+        assert_eq!(gini(&[]), 0);
+        assert_eq!(gini(&[0, 0, 0]), 0);
+        assert_eq!(gini(&[7]), 0);
+        assert_eq!(gini(&[1_000; 150]), 0);
+    }
+
+    #[test]
+    fn gini_of_one_holder_is_close_to_one() {
+        let mut powers = vec![0; 999];
+        powers.push(5_000_000);
+        // (n - 1) / n
+        assert_eq!(gini(&powers), 999_000);
+        powers.reverse();
+        assert_eq!(gini(&powers), 999_000);
+    }
+
+    #[test]
+    fn gini_between() {
+        // Mean absolute difference 2/3 over twice the mean 2
+        assert_eq!(gini(&[1, 2, 3]), 222_222);
+    }
+}
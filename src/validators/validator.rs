@@ -1,12 +1,58 @@
+use clap::ValueEnum;
 use crate::functions::format_to_millions;
 
-#[derive(Clone, Debug, serde::Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidatorStatus {
+    Active,
+    Jailed,
+    Inactive,
+    /// `nomic validators` did not report a status
+    #[default]
+    #[value(skip)]
+    Unknown,
+}
+
+impl ValidatorStatus {
+    /// Parses a `STATUS:` value, e.g. active, bonded, jailed, unbonding
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim().to_lowercase();
+        if value.contains("jail") {
+            Self::Jailed
+        } else if value.starts_with("inactive") || value.starts_with("unbond") {
+            Self::Inactive
+        } else if value.starts_with("active") || value.starts_with("bonded") {
+            Self::Active
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl std::fmt::Display for ValidatorStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            ValidatorStatus::Active   => "active",
+            ValidatorStatus::Jailed   => "jailed",
+            ValidatorStatus::Inactive => "inactive",
+            ValidatorStatus::Unknown  => "unknown",
+        };
+        write!(f, "{}", output)
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct Validator {
     rank: u64,
     address: String,
     voting_power: u64,
     moniker: String,
     details: String,
+    #[serde(default)]
+    status: ValidatorStatus,
+    /// Commission rate in ppm, if reported
+    #[serde(default)]
+    commission: Option<u64>,
 }
 
 #[derive(Clone, tabled::Tabled)]
@@ -57,9 +103,21 @@ impl Validator {
             voting_power,
             moniker,
             details,
+            status: ValidatorStatus::Unknown,
+            commission: None,
         }
     }
 
+    pub fn with_status(mut self, status: ValidatorStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_commission(mut self, commission: Option<u64>) -> Self {
+        self.commission = commission;
+        self
+    }
+
     // Getter for rank
     pub fn rank(&self) -> u64 {
         self.rank
@@ -85,6 +143,16 @@ impl Validator {
         &self.details
     }
 
+    // Getter for status
+    pub fn status(&self) -> ValidatorStatus {
+        self.status
+    }
+
+    // Getter for commission in ppm
+    pub fn commission(&self) -> Option<u64> {
+        self.commission
+    }
+
     pub fn voting_power_nom(&self) -> String {
         // Converts voting power to NOM (e.g., from uNOM to NOM)
         format_to_millions(self.voting_power, Some(0))
@@ -0,0 +1,46 @@
+use crate::validators::{Validator, ValidatorCollection};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Compares indexed validator lookups with a linear scan over a synthetic validator list
+pub fn validator_lookups(count: usize, lookups: usize) {
+    let validators: Vec<Validator> = (0..count)
+        .map(|i| Validator::new(
+            i as u64 + 1,
+            format!("nomicvaloper1{:038}", i),
+            (count - i) as u64 * 1_000_000,
+            format!("Validator {}", i),
+            String::new(),
+        ))
+        .collect();
+
+    let start = Instant::now();
+    let collection = ValidatorCollection::from_vec(validators.clone());
+    let build = start.elapsed();
+
+    // Spread the lookups over the whole list, the scan's average case
+    let addresses: Vec<String> = (0..lookups)
+        .map(|i| format!("nomicvaloper1{:038}", (i * 7919) % count.max(1)))
+        .collect();
+
+    let start = Instant::now();
+    for address in &addresses {
+        black_box(validators.iter().find(|v| v.address().to_lowercase() == address.to_lowercase()));
+    }
+    let scan = start.elapsed();
+
+    let start = Instant::now();
+    for address in &addresses {
+        black_box(collection.validator(address).ok());
+    }
+    let indexed = start.elapsed();
+
+    let per_lookup = |elapsed: Duration| elapsed.as_nanos() / lookups.max(1) as u128;
+    println!("{} validators, {} lookups", count, lookups);
+    println!("{:12} {:>12?}", "build index", build);
+    println!("{:12} {:>12?} {:>8} ns/lookup", "linear scan", scan, per_lookup(scan));
+    println!("{:12} {:>12?} {:>8} ns/lookup", "indexed", indexed, per_lookup(indexed));
+    if indexed.as_nanos() > 0 {
+        println!("{:12} {:>11.1}x", "speedup", scan.as_secs_f64() / indexed.as_secs_f64());
+    }
+}
@@ -1,5 +1,6 @@
 use clap::Parser;
 use clap::Subcommand;
+use crate::z::bench;
 use eyre::Result;
 
 #[derive(Parser)]
@@ -12,6 +13,16 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum CliCommand {
     Test,
+    /// Benchmark validator lookups on a synthetic validator list
+    BenchValidators {
+        /// Number of synthetic validators
+        #[arg(short = 'n', long, default_value_t = 10_000)]
+        count: usize,
+
+        /// Number of address lookups to time
+        #[arg(short = 'l', long, default_value_t = 100_000)]
+        lookups: usize,
+    },
 }
 
 impl Cli {
@@ -26,6 +37,10 @@ impl Cli {
                 return Ok(());
             },
 
+            Some(CliCommand::BenchValidators { count, lookups }) => {
+                bench::validator_lookups(*count, *lookups);
+            },
+
             // Default case when no subcommand is provided
             None => {
                 println!("No command selected");
@@ -1,3 +1,4 @@
+mod bench;
 mod cli;
 
 pub use cli::Cli;